@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use crate::config::Params;
+use crate::context::Context;
+use crate::memory::{format_bytes, Usage};
+use crate::runner::{self, print_table};
+use crate::solution::{Parts, Phase, Runner, Timing};
+
+/// A median this much slower than the baseline's is reported as a regression
+const REGRESSION_THRESHOLD: f64 = 0.10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        let median = match sorted.len() % 2 {
+            0 => (sorted[mid - 1] + sorted[mid]) / 2,
+            _ => sorted[mid],
+        };
+        let secs: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+        Some(Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+pub struct Report {
+    pub name: &'static str,
+    pub year: u16,
+    pub runs: usize,
+    pub phases: Vec<(Phase, Stats)>,
+    /// Allocations of each phase in the last run, empty without the `alloc-stats` feature
+    pub memory: Vec<(Phase, Usage)>,
+}
+
+/// Runs `solver` `warmup` times untimed, then `runs` times collecting the timing of every phase.
+/// Every run gets `timeout` to finish, a run that times out or panics fails the whole bench.
+pub fn bench(
+    solver: &dyn Runner,
+    input: &str,
+    timeout: Option<Duration>,
+    params: &Params,
+    warmup: usize,
+    runs: usize,
+) -> anyhow::Result<Report> {
+    // no progress bars, drawing them would skew the timings
+    let ctx = Context::default()
+        .with_timeout(timeout)
+        .with_params(params.clone());
+    let run = |timings: &mut Vec<Timing>| -> anyhow::Result<()> {
+        let result = runner::run_input(solver, input, Parts::Both, &ctx, timings)?;
+        match result.errors().first() {
+            Some((phase, e)) => Err(anyhow!("part {}: {:#}", phase, e)),
+            None => Ok(()),
+        }
+    };
+    for _ in 0..warmup {
+        run(&mut Vec::new())?;
+    }
+    let mut samples: Vec<(Phase, Vec<Duration>)> = Vec::new();
+    let mut memory = Vec::new();
+    for _ in 0..runs {
+        let mut timings = Vec::new();
+        run(&mut timings)?;
+        memory = timings
+            .iter()
+            .filter_map(|timing| Some((timing.phase, timing.memory?)))
+            .collect();
+        for timing in timings {
+            match samples.iter_mut().find(|(phase, _)| *phase == timing.phase) {
+                Some((_, durations)) => durations.push(timing.elapsed),
+                None => samples.push((timing.phase, vec![timing.elapsed])),
+            }
+        }
+    }
+    Ok(Report {
+        name: solver.name(),
+        year: solver.year(),
+        runs,
+        phases: samples
+            .iter()
+            .filter_map(|(phase, durations)| Some((*phase, Stats::new(durations)?)))
+            .collect(),
+        memory,
+    })
+}
+
+/// Saved stats keyed by (year, solution name, phase)
+pub type Baseline = HashMap<(u16, String, String), Stats>;
+
+/// Reads a baseline written by `save_baseline`, a missing file is an empty baseline
+pub fn load_baseline(path: &Path) -> anyhow::Result<Baseline> {
+    if !path.exists() {
+        return Ok(Baseline::new());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow!("Unable to read {:?}: {}", path, e))?;
+    parse_baseline(&content).map_err(|e| anyhow!("{:?}: {}", path, e))
+}
+
+/// One `<year> <name> <phase> <min> <median> <mean> <stddev>` line per phase, durations in
+/// nanoseconds
+fn parse_baseline(content: &str) -> anyhow::Result<Baseline> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [year, name, phase, min, median, mean, stddev] = fields[..] else {
+                return Err(anyhow!("line {}: expected 7 fields", i + 1));
+            };
+            let year: u16 = year
+                .parse()
+                .map_err(|e| anyhow!("line {}: {:?} is not a year: {}", i + 1, year, e))?;
+            let nanos = |field: &str| -> anyhow::Result<Duration> {
+                Ok(Duration::from_nanos(field.parse().map_err(|e| {
+                    anyhow!("line {}: {:?} is not a duration: {}", i + 1, field, e)
+                })?))
+            };
+            let stats = Stats {
+                min: nanos(min)?,
+                median: nanos(median)?,
+                mean: nanos(mean)?,
+                stddev: nanos(stddev)?,
+            };
+            Ok(((year, name.to_string(), phase.to_string()), stats))
+        })
+        .collect()
+}
+
+fn format_baseline(baseline: &Baseline) -> String {
+    let mut lines: Vec<String> = baseline
+        .iter()
+        .map(|((year, name, phase), stats)| {
+            format!(
+                "{} {} {} {} {} {} {}",
+                year,
+                name,
+                phase,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.stddev.as_nanos()
+            )
+        })
+        .collect();
+    lines.sort();
+    format!(
+        "# year name phase min median mean stddev (nanoseconds)\n{}\n",
+        lines.join("\n")
+    )
+}
+
+/// Stores the reports in `path`, keeping entries of days that were not benchmarked this time
+pub fn save_baseline(path: &Path, reports: &[Report]) -> anyhow::Result<()> {
+    let mut baseline = load_baseline(path)?;
+    for report in reports {
+        for (phase, stats) in &report.phases {
+            let key = (report.year, report.name.to_string(), phase.to_string());
+            baseline.insert(key, *stats);
+        }
+    }
+    fs::write(path, format_baseline(&baseline))
+        .map_err(|e| anyhow!("Unable to write {:?}: {}", path, e))
+}
+
+fn change(current: &Stats, previous: &Stats) -> String {
+    let ratio = current.median.as_secs_f64() / previous.median.as_secs_f64() - 1.0;
+    let marker = if ratio > REGRESSION_THRESHOLD {
+        " REGRESSION"
+    } else {
+        ""
+    };
+    format!("{:+.1}%{}", ratio * 100.0, marker)
+}
+
+/// Prints a table per report, comparing medians against `baseline` when one is given.
+/// Returns the number of phases that regressed.
+pub fn print_reports(reports: &[Report], baseline: Option<&Baseline>) -> usize {
+    let mut regressions = 0;
+    for report in reports {
+        println!("{} ({} runs)", report.name, report.runs);
+        let rows: Vec<Vec<String>> = report
+            .phases
+            .iter()
+            .map(|(phase, stats)| {
+                let previous = baseline.and_then(|baseline| {
+                    baseline.get(&(report.year, report.name.to_string(), phase.to_string()))
+                });
+                let vs_baseline =
+                    previous.map_or("-".to_string(), |previous| change(stats, previous));
+                regressions += vs_baseline.ends_with("REGRESSION") as usize;
+                let mut row = vec![
+                    phase.to_string(),
+                    format!("{:.2?}", stats.min),
+                    format!("{:.2?}", stats.median),
+                    format!("{:.2?}", stats.mean),
+                    format!("{:.2?}", stats.stddev),
+                    vs_baseline,
+                ];
+                if let Some((_, usage)) = report.memory.iter().find(|(p, _)| p == phase) {
+                    row.push(format_bytes(usage.peak));
+                    row.push(usage.allocations.to_string());
+                    row.push(format_bytes(usage.total));
+                }
+                row
+            })
+            .collect();
+        let mut headers = vec!["phase", "min", "median", "mean", "stddev", "vs baseline"];
+        if !report.memory.is_empty() {
+            headers.extend(["peak", "allocs", "allocated"]);
+        }
+        print_table(&headers, &rows);
+        println!();
+    }
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = [4, 1, 3, 2].map(Duration::from_millis).to_vec();
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.stddev.as_micros(), 1118);
+        assert_eq!(Stats::new(&[]), None);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let stats = Stats {
+            min: Duration::from_nanos(10),
+            median: Duration::from_nanos(12),
+            mean: Duration::from_nanos(13),
+            stddev: Duration::from_nanos(2),
+        };
+        let baseline = Baseline::from_iter(vec![
+            ((2023, "d4".to_string(), "parse".to_string()), stats),
+            ((2023, "d1".to_string(), "1".to_string()), stats),
+            ((2024, "d1".to_string(), "1".to_string()), stats),
+        ]);
+        assert_eq!(
+            parse_baseline(&format_baseline(&baseline)).unwrap(),
+            baseline
+        );
+        assert!(parse_baseline("2023 d4 parse 1 2 3").is_err());
+        assert!(parse_baseline("d4 parse 1 2 3 4").is_err());
+    }
+
+    #[test]
+    fn test_change() {
+        let stats = |median| Stats {
+            min: Duration::ZERO,
+            median: Duration::from_millis(median),
+            mean: Duration::ZERO,
+            stddev: Duration::ZERO,
+        };
+        assert_eq!(change(&stats(105), &stats(100)), "+5.0%");
+        assert_eq!(change(&stats(150), &stats(100)), "+50.0% REGRESSION");
+        assert_eq!(change(&stats(50), &stats(100)), "-50.0%");
+    }
+}
@@ -1,11 +1,263 @@
-use crate::solution::Day;
-use clap::Parser;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
 
-/// Simple program to greet a person
+use aoc_2023::config::{self, Config};
+use aoc_2023::ledger::Verdict;
+use aoc_2023::registry;
+use aoc_2023::report::Format;
+use aoc_2023::solution::Parts;
+use clap::builder::PossibleValuesParser;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{ArgAction, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::Shell;
+use log::LevelFilter;
+
+/// Solves Advent of Code puzzles and times, checks and records their answers
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Args {
-    /// Which AOC day + part to run
-    #[arg()]
-    pub(crate) solution: Day,
+    /// Which AOC day to run
+    #[arg(required = true, value_parser = PossibleValuesParser::new(registry::names()))]
+    pub(crate) solution: Option<String>,
+
+    /// Which part of the day to run
+    #[arg(long, value_enum, default_value_t = Parts::Both)]
+    pub(crate) part: Parts,
+
+    /// Read the input from this file instead of the inputs directory, `-` reads stdin
+    #[arg(long)]
+    pub(crate) input: Option<PathBuf>,
+
+    /// Keep running, re-running the day whenever its input file changes
+    #[arg(long)]
+    pub(crate) watch: bool,
+
+    /// Read defaults for the options below from this file [default: ./aoc.toml, if it exists]
+    #[arg(long, global = true)]
+    pub(crate) config: Option<PathBuf>,
+
+    /// Which year's puzzles to run [default: 2023]
+    #[arg(long, global = true)]
+    pub(crate) year: Option<u16>,
+
+    /// Directory holding the inputs, as `<year>/<solution>.txt` [default: ./inputs/]
+    #[arg(long, global = true)]
+    pub(crate) inputs_dir: Option<PathBuf>,
+
+    /// Directory the answers are written to [default: ./outputs/]
+    #[arg(long, global = true)]
+    pub(crate) outputs_dir: Option<PathBuf>,
+
+    /// Whether to write the answers to the outputs directory [default: true]
+    #[arg(long, global = true, value_name = "BOOL")]
+    pub(crate) write_outputs: Option<bool>,
+
+    /// Print more diagnostics, repeat for more detail (`-v` info, `-vv` debug, `-vvv` trace)
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    pub(crate) verbose: u8,
+
+    /// Only print answers and errors, without progress bars
+    #[arg(short, long, global = true)]
+    pub(crate) quiet: bool,
+
+    /// How to print results, `json` and `csv` emit one record per day and part [default: text]
+    #[arg(long, global = true, value_enum)]
+    pub(crate) format: Option<Format>,
+
+    /// File of submitted answers and their verdicts, runs warn about answers it already rules out
+    #[arg(long, global = true, default_value = "./ledger.txt")]
+    pub(crate) ledger: PathBuf,
+
+    /// Stop a day that runs longer than this, e.g. `30s` or `2m`, and report it as TIMEOUT
+    #[arg(long, global = true, value_parser = humantime::parse_duration)]
+    pub(crate) timeout: Option<Duration>,
+
+    /// Set a day's parameter, `key=value` for every day taking `key` or `d11.key=value` for one day
+    #[arg(long = "param", global = true, value_name = "KEY=VALUE")]
+    pub(crate) params: Vec<String>,
+
+    #[command(subcommand)]
+    pub(crate) command: Option<Command>,
+}
+
+impl Args {
+    /// Parses the command line like `try_parse_from`, but rejects a day to run, or an option only
+    /// running one takes, given alongside a subcommand. Global options can go on either side of it.
+    pub(crate) fn try_parse_args<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let mut command = Args::command();
+        let matches = command.try_get_matches_from_mut(args)?;
+        if let Some(subcommand) = matches.subcommand_name() {
+            for id in ["solution", "part", "input", "watch"] {
+                if matches.value_source(id) != Some(ValueSource::CommandLine) {
+                    continue;
+                }
+                let arg = command
+                    .get_arguments()
+                    .find(|arg| arg.get_id() == id)
+                    .map_or(id.to_string(), |arg| arg.to_string());
+                let message = format!(
+                    "the argument '{}' cannot be used with the '{}' subcommand",
+                    arg, subcommand
+                );
+                return Err(command.error(ErrorKind::ArgumentConflict, message));
+            }
+        }
+        Args::from_arg_matches(&matches).map_err(|e| e.format(&mut command))
+    }
+
+    pub(crate) fn log_level(&self) -> LevelFilter {
+        match (self.quiet, self.verbose) {
+            (true, _) => LevelFilter::Error,
+            (false, 0) => LevelFilter::Warn,
+            (false, 1) => LevelFilter::Info,
+            (false, 2) => LevelFilter::Debug,
+            (false, _) => LevelFilter::Trace,
+        }
+    }
+
+    /// The config file with the options given on the command line applied over it
+    pub(crate) fn config(&self) -> anyhow::Result<Config> {
+        let mut config = config::load(self.config.as_deref())?;
+        if let Some(year) = self.year {
+            config.year = year;
+        }
+        if let Some(dir) = &self.inputs_dir {
+            config.inputs_dir = dir.clone();
+        }
+        if let Some(dir) = &self.outputs_dir {
+            config.outputs_dir = dir.clone();
+        }
+        if let Some(write) = self.write_outputs {
+            config.write_outputs = write;
+        }
+        if let Some(format) = self.format {
+            config.format = format;
+        }
+        if self.timeout.is_some() {
+            config.timeout = self.timeout;
+        }
+        for param in &self.params {
+            config.set_param(&registry::for_year(config.year), param)?;
+        }
+        config.check_params(registry::SOLVERS)?;
+        Ok(config)
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run every day of the year and print a summary table in day order
+    RunAll {
+        /// Number of days to run at once, each on its own thread
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: u32,
+    },
+    /// Compare answers against a file of known-correct answers
+    Verify {
+        /// Days to verify, all of them if none are given
+        #[arg(value_parser = PossibleValuesParser::new(registry::names()))]
+        solutions: Vec<String>,
+
+        /// File with one `<year>.<output name> = <answer>` per line, e.g. `2023.d4p1 = 24542`
+        #[arg(long, default_value = "./answers.txt")]
+        answers: PathBuf,
+    },
+    /// Check each day against the examples from its puzzle text, no input files needed
+    Examples {
+        /// Days to check, all of them if none are given
+        #[arg(value_parser = PossibleValuesParser::new(registry::names()))]
+        solutions: Vec<String>,
+    },
+    /// Record the verdict on a submitted answer in the ledger
+    Record {
+        #[arg(value_parser = PossibleValuesParser::new(registry::names()))]
+        solution: String,
+
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        #[arg(value_enum)]
+        verdict: Verdict,
+
+        /// The submitted answer, defaults to the one last written to the outputs directory
+        answer: Option<String>,
+    },
+    /// Time days over repeated runs, reporting parse and each part separately
+    Bench {
+        #[arg(required = true, value_parser = PossibleValuesParser::new(registry::names()))]
+        solutions: Vec<String>,
+
+        /// Number of timed runs
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+
+        /// Untimed runs before measuring
+        #[arg(long, default_value_t = 3)]
+        warmup: u32,
+
+        /// Compare medians against timings saved earlier with `--save-baseline`
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Save the timings to this file, replacing any saved for the same days
+        #[arg(long)]
+        save_baseline: Option<PathBuf>,
+    },
+    /// Generate and register a skeleton for a new day of `--year`, run from the repository root
+    NewDay {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Print a completion script, e.g. `source <(aoc-2023 completions bash)`
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Print the man page, or write one page per subcommand into a directory
+    Manpage {
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_options_around_subcommands() {
+        let args = Args::try_parse_args(["aoc", "-v", "examples", "d4"]).unwrap();
+        assert_eq!(args.verbose, 1);
+        assert!(matches!(args.command, Some(Command::Examples { .. })));
+
+        let args = Args::try_parse_args(["aoc", "--format", "json", "run-all"]).unwrap();
+        assert_eq!(args.format, Some(Format::Json));
+        let args = Args::try_parse_args(["aoc", "--year", "2024", "new-day", "1"]).unwrap();
+        assert_eq!(args.year, Some(2024));
+        let args = Args::try_parse_args(["aoc", "run-all", "--timeout", "5s"]).unwrap();
+        assert_eq!(args.timeout, Some(Duration::from_secs(5)));
+
+        let args = Args::try_parse_args(["aoc", "-q", "d4", "--part", "1"]).unwrap();
+        assert_eq!(args.solution.as_deref(), Some("d4"));
+        assert!(Args::try_parse_args(["aoc", "-q"]).is_err());
+    }
+
+    #[test]
+    fn test_day_conflicts_with_subcommands() {
+        for args in [
+            vec!["aoc", "d4", "run-all"],
+            vec!["aoc", "--part", "1", "run-all"],
+            vec!["aoc", "--watch", "examples"],
+        ] {
+            let error = Args::try_parse_args(args).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+        }
+    }
 }
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Deserializer};
+
+use crate::registry;
+use crate::report::Format;
+use crate::solution::{Param, Runner};
+
+/// Parameters for each day, by day name and then parameter name
+pub type Params = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Runner defaults read from `aoc.toml`, flags given on the command line override them
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub inputs_dir: PathBuf,
+    pub outputs_dir: PathBuf,
+    /// Whether runs write their answers to the outputs directory
+    pub write_outputs: bool,
+    pub year: u16,
+    pub format: Format,
+    #[serde(deserialize_with = "duration")]
+    pub timeout: Option<Duration>,
+    #[serde(deserialize_with = "params")]
+    pub params: Params,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            inputs_dir: PathBuf::from("./inputs/"),
+            outputs_dir: PathBuf::from("./outputs/"),
+            write_outputs: true,
+            year: registry::DEFAULT_YEAR,
+            format: Format::Text,
+            timeout: None,
+            params: Params::new(),
+        }
+    }
+}
+
+/// A duration like `30s` or `2m`
+fn duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    humantime::parse_duration(&text)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Parameter values are kept as text, days parse them when they read them
+fn params<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Params, D::Error> {
+    let tables = BTreeMap::<String, BTreeMap<String, toml::Value>>::deserialize(deserializer)?;
+    Ok(tables
+        .into_iter()
+        .map(|(day, values)| {
+            let values = values
+                .into_iter()
+                .map(|(key, value)| match value {
+                    toml::Value::String(text) => (key, text),
+                    value => (key, value.to_string()),
+                })
+                .collect();
+            (day, values)
+        })
+        .collect())
+}
+
+impl Config {
+    /// Applies a `--param`: `day.key=value` sets it for that day, `key=value` for every day of
+    /// `solvers` that declares `key`
+    pub fn set_param(&mut self, solvers: &[&dyn Runner], param: &str) -> anyhow::Result<()> {
+        let (key, value) = param
+            .split_once('=')
+            .ok_or_else(|| anyhow!("{:?} is not KEY=VALUE", param))?;
+        let days: Vec<&str> = match key.split_once('.') {
+            Some((day, _)) => vec![day],
+            None => solvers
+                .iter()
+                .filter(|solver| solver.params().iter().any(|p| p.name == key))
+                .map(|solver| solver.name())
+                .collect(),
+        };
+        if days.is_empty() {
+            return Err(anyhow!("no day takes a param {}", key));
+        }
+        let key = key.split_once('.').map_or(key, |(_, key)| key);
+        for day in days {
+            self.params
+                .entry(day.to_string())
+                .or_default()
+                .insert(key.to_string(), value.to_string());
+        }
+        Ok(())
+    }
+
+    /// Fails on any parameter the named day of `solvers` doesn't declare
+    pub fn check_params(&self, solvers: &[&dyn Runner]) -> anyhow::Result<()> {
+        for (day, values) in &self.params {
+            let declared: Vec<&Param> = solvers
+                .iter()
+                .filter(|solver| solver.name() == day)
+                .flat_map(|solver| solver.params())
+                .collect();
+            for key in values.keys() {
+                if !declared.iter().any(|param| param.name == key) {
+                    return Err(match declared.is_empty() {
+                        true => anyhow!("{} takes no params, got {}", day, key),
+                        false => anyhow!(
+                            "{} has no param {}, it takes (shown with their defaults):\n{}",
+                            day,
+                            key,
+                            declared
+                                .iter()
+                                .map(|param| format!("  {}", param))
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_config(content: &str) -> anyhow::Result<Config> {
+    Ok(toml::from_str(content)?)
+}
+
+/// Read when `--config` isn't given
+pub const DEFAULT_PATH: &str = "./aoc.toml";
+
+/// Reads the config at `path`, or at [`DEFAULT_PATH`] where a missing file leaves every default
+/// in place
+pub fn load(path: Option<&Path>) -> anyhow::Result<Config> {
+    let path = match path {
+        Some(path) => path,
+        None if !Path::new(DEFAULT_PATH).exists() => return Ok(Config::default()),
+        None => Path::new(DEFAULT_PATH),
+    };
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow!("Unable to read {:?}: {}", path, e))?;
+    parse_config(&content).map_err(|e| anyhow!("{:?}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse_config(
+            r#"
+inputs-dir = "puzzles"
+write-outputs = false
+format = "csv"
+timeout = "1m 30s"
+
+[params.d11]
+expansion = 10
+
+[params.d2]
+red = 20
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.inputs_dir, PathBuf::from("puzzles"));
+        assert_eq!(config.outputs_dir, PathBuf::from("./outputs/"));
+        assert!(!config.write_outputs);
+        assert_eq!(config.year, registry::DEFAULT_YEAR);
+        assert_eq!(config.format, Format::Csv);
+        assert_eq!(config.timeout, Some(Duration::from_secs(90)));
+        assert_eq!(config.params["d11"]["expansion"], "10");
+        assert_eq!(config.params["d2"]["red"], "20");
+
+        assert_eq!(parse_config("").unwrap(), Config::default());
+        assert!(parse_config("input-dir = \"typo\"").is_err());
+        assert!(parse_config("timeout = \"soon\"").is_err());
+    }
+
+    #[test]
+    fn test_load_explicit_missing_file() {
+        assert!(load(Some(Path::new("no/such/aoc.toml"))).is_err());
+    }
+
+    #[test]
+    fn test_set_param() {
+        let solvers = registry::for_year(2023);
+        let mut config = Config::default();
+        config.set_param(&solvers, "expansion=10").unwrap();
+        config.set_param(&solvers, "d8.end=XYZ").unwrap();
+        assert_eq!(config.params["d11"]["expansion"], "10");
+        assert_eq!(config.params["d8"]["end"], "XYZ");
+        assert!(config.check_params(registry::SOLVERS).is_ok());
+
+        assert!(config.set_param(&solvers, "expansion").is_err());
+        assert!(config.set_param(&solvers, "nothing=1").is_err());
+        config.set_param(&solvers, "d11.expanse=1").unwrap();
+        assert_eq!(
+            config
+                .check_params(registry::SOLVERS)
+                .unwrap_err()
+                .to_string(),
+            "d11 has no param expanse, it takes (shown with their defaults):\n  \
+             expansion=1000000  How many rows or columns each empty one becomes in part 2"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use tqdm::{tqdm, Tqdm};
+
+use crate::config::Params;
+use crate::solution::Param;
+
+/// Handed to every part while it runs, lets long loops report their progress and notice
+/// when they ran out of time
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    show_progress: bool,
+    timeout: Option<Duration>,
+    cancelled: Arc<AtomicBool>,
+    params: Arc<Params>,
+    /// The day whose parameters [`Context::param`] reads
+    day: &'static str,
+    declared: &'static [Param],
+}
+
+/// The error [`Context::check`] stops a day with once its timeout ran out
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimedOut(pub Duration);
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {}", humantime::format_duration(self.0))
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Counts down a day's timeout on a background thread, dropping it stops the countdown
+pub struct Deadline {
+    _stop: Option<mpsc::Sender<()>>,
+}
+
+impl Context {
+    /// `show_progress` should only be set when stderr is a terminal, the bars are drawn there
+    pub fn new(show_progress: bool) -> Self {
+        Context {
+            show_progress,
+            ..Context::default()
+        }
+    }
+
+    /// Gives every day `timeout` to finish, counted from [`Context::start`]
+    pub fn with_timeout(self, timeout: Option<Duration>) -> Self {
+        Context { timeout, ..self }
+    }
+
+    /// Hands every day its parameters, keyed by day name
+    pub fn with_params(self, params: Params) -> Self {
+        Context {
+            params: Arc::new(params),
+            ..self
+        }
+    }
+
+    /// A copy of this context that reads the parameters of `day`, which declares `declared`
+    pub fn for_day(&self, day: &'static str, declared: &'static [Param]) -> Context {
+        Context {
+            day,
+            declared,
+            ..self.clone()
+        }
+    }
+
+    /// The day's parameter `key` if one was given, else its declared default
+    pub fn param<T>(&self, key: &str) -> anyhow::Result<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let declared = self
+            .declared
+            .iter()
+            .find(|param| param.name == key)
+            .ok_or_else(|| anyhow!("{} reads param {} without declaring it", self.day, key))?;
+        let value = self
+            .params
+            .get(self.day)
+            .and_then(|params| params.get(key))
+            .map_or(declared.default, String::as_str);
+        value
+            .parse()
+            .map_err(|e| anyhow!("{} param {} = {:?}: {}", self.day, key, value, e))
+    }
+
+    /// A copy of this context for one day's run, cancelled once the timeout runs out
+    pub fn start(&self) -> (Context, Deadline) {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let stop = self.timeout.map(|timeout| {
+            let (stop, stopped) = mpsc::channel::<()>();
+            let cancelled = cancelled.clone();
+            thread::spawn(move || {
+                if let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(timeout) {
+                    cancelled.store(true, Ordering::Relaxed);
+                }
+            });
+            stop
+        });
+        let ctx = Context {
+            cancelled,
+            ..self.clone()
+        };
+        (ctx, Deadline { _stop: stop })
+    }
+
+    /// Fails with [`TimedOut`] once the day's timeout ran out, long loops should call this as they go
+    pub fn check(&self) -> anyhow::Result<()> {
+        match self.cancelled.load(Ordering::Relaxed) {
+            true => Err(TimedOut(self.timeout.unwrap_or_default()).into()),
+            false => Ok(()),
+        }
+    }
+
+    /// Wraps a long loop of `total` items, drawing a progress bar labelled `label` when enabled.
+    /// The bar is cleared once the loop is done.
+    pub fn progress<I: Iterator>(&self, label: &str, total: usize, iter: I) -> Progress<I> {
+        match self.show_progress {
+            true => Progress::Bar(tqdm(Counted { iter, total }).desc(Some(label)).clear(true)),
+            false => Progress::Hidden(iter),
+        }
+    }
+}
+
+/// An iterator that reports through a progress bar, or just passes items through
+pub enum Progress<I: Iterator> {
+    Hidden(I),
+    Bar(Tqdm<I::Item, Counted<I>>),
+}
+
+impl<I: Iterator> Iterator for Progress<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Progress::Hidden(iter) => iter.next(),
+            Progress::Bar(iter) => iter.next(),
+        }
+    }
+}
+
+/// Gives tqdm the total it reads from `size_hint`, which adapters like `flat_map` lose
+pub struct Counted<I> {
+    iter: I,
+    total: usize,
+}
+
+impl<I: Iterator> Iterator for Counted<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.total = self.total.saturating_sub(1);
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.total, Some(self.total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_passes_items_through() {
+        let hidden: Vec<_> = Context::default().progress("hidden", 3, 0..3).collect();
+        assert_eq!(hidden, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_param() {
+        let params = Params::from([(
+            "d11".to_string(),
+            [("expansion".to_string(), "10".to_string())].into(),
+        )]);
+        let declared = &[Param {
+            name: "expansion",
+            default: "2",
+            doc: "",
+        }];
+        let ctx = Context::default().with_params(params);
+        assert_eq!(
+            ctx.for_day("d11", declared).param("expansion").ok(),
+            Some(10)
+        );
+        assert_eq!(ctx.for_day("d2", declared).param("expansion").ok(), Some(2));
+        let error = ctx.for_day("d11", declared).param::<bool>("expansion");
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "d11 param expansion = \"10\": provided string was not `true` or `false`"
+        );
+        let error = ctx.for_day("d11", declared).param::<u32>("other");
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "d11 reads param other without declaring it"
+        );
+    }
+
+    #[test]
+    fn test_timeout_cancels() {
+        let ctx = Context::default().with_timeout(Some(Duration::from_millis(10)));
+        let (started, _deadline) = ctx.start();
+        assert!(started.check().is_ok());
+        thread::sleep(Duration::from_millis(100));
+        let error = started.check().unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<TimedOut>(),
+            Some(&TimedOut(Duration::from_millis(10)))
+        );
+        assert!(ctx.check().is_ok());
+
+        let (finished, deadline) = ctx.start();
+        drop(deadline);
+        thread::sleep(Duration::from_millis(100));
+        assert!(finished.check().is_ok());
+    }
+}
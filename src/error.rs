@@ -0,0 +1,207 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use nom::error::ErrorKind;
+
+/// A puzzle input that could not be parsed, pointing at the offending position
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    /// The full line the error is on
+    pub source_line: String,
+    /// The rest of the line starting at the error position
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error at `remaining`, which must be a suffix or a sub-slice of `input`
+    pub fn new(input: &str, remaining: &str, expected: impl Into<String>) -> Self {
+        let offset = (remaining.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| *offset <= input.len())
+            .unwrap_or(input.len() - remaining.len().min(input.len()));
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        ParseError {
+            file: None,
+            line: before.matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            source_line: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            found: input[offset..line_end].trim_end_matches('\r').to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Converts a failed nom parse of `input`, `expected` describes what the parser was looking for
+    pub fn from_nom(
+        input: &str,
+        err: nom::Err<nom::error::Error<&str>>,
+        expected: impl Into<String>,
+    ) -> Self {
+        let expected = expected.into();
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => ParseError::new(
+                input,
+                e.input,
+                format!("{} ({})", expected, describe(&e.code)),
+            ),
+            nom::Err::Incomplete(_) => ParseError::new(
+                input,
+                &input[input.len()..],
+                format!("{} (more input)", expected),
+            ),
+        }
+    }
+
+    pub fn in_file(self, file: &Path) -> Self {
+        ParseError {
+            file: Some(file.to_path_buf()),
+            ..self
+        }
+    }
+
+    /// Moves the error down by `lines`, for input that had lines dropped before it was parsed
+    pub fn below(self, lines: usize) -> Self {
+        ParseError {
+            line: self.line + lines,
+            ..self
+        }
+    }
+}
+
+fn describe(kind: &ErrorKind) -> &str {
+    match kind {
+        ErrorKind::Tag => "a literal token",
+        ErrorKind::Char => "a specific character",
+        ErrorKind::OneOf => "one of the allowed characters",
+        ErrorKind::Digit => "a number",
+        ErrorKind::Alpha => "a word",
+        ErrorKind::Eof => "more input",
+        ErrorKind::CrLf => "a line break",
+        ErrorKind::Verify => "a valid value",
+        ErrorKind::MapRes => "a value in range",
+        kind => kind.description(),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) if file == Path::new("-") => write!(f, "<stdin>:")?,
+            Some(file) => write!(f, "{}:", file.display())?,
+            None => write!(f, "input:")?,
+        }
+        let found = match self.found.as_str() {
+            "" => "end of line".to_string(),
+            found => format!("{:?}", found),
+        };
+        writeln!(
+            f,
+            "{}:{}: expected {}, found {}",
+            self.line, self.column, self.expected, found
+        )?;
+        writeln!(f, "    {}", self.source_line)?;
+        write!(f, "    {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Checks that a parser left nothing but whitespace, positions are reported relative to `input`
+fn finish<'a, T>(
+    input: &'a str,
+    result: nom::IResult<&'a str, T>,
+    expected: &str,
+) -> Result<T, ParseError> {
+    let (rest, parsed) = result.map_err(|e| ParseError::from_nom(input, e, expected))?;
+    if rest.trim().is_empty() {
+        Ok(parsed)
+    } else {
+        Err(ParseError::new(input, rest.trim_start(), expected))
+    }
+}
+
+/// Runs `parser` over the whole of `input`, anything but trailing whitespace left over is an error
+/// pointing at the first unparsed token
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> nom::IResult<&'a str, T>,
+    expected: &str,
+) -> Result<T, ParseError> {
+    finish(input, parser(input), expected)
+}
+
+/// Runs `parser` over every non-blank line of `input`, each line has to be consumed entirely
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> nom::IResult<&'a str, T>,
+    expected: &str,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| finish(input, parser(line), expected))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::character::complete::{digit1, newline};
+    use nom::multi::separated_list1;
+
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let input = "12 34\n56 x8\n";
+        let error = ParseError::new(input, &input[9..], "a number");
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.source_line, "56 x8");
+        assert_eq!(error.found, "x8");
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let numbers = |input| separated_list1(newline, digit1)(input);
+        assert_eq!(parse_all("1\n2\n", numbers, "numbers"), Ok(vec!["1", "2"]));
+
+        let error = parse_all("1\n2\nthree\n", numbers, "numbers").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.found, "three");
+
+        let error = parse_all("x", numbers, "numbers").unwrap_err();
+        assert_eq!(error.expected, "numbers (a number)");
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "\n12\n\n34\n5x\n";
+        assert_eq!(
+            parse_lines("12\n\n34\n", digit1, "a number"),
+            Ok(vec!["12", "34"])
+        );
+        let error = parse_lines(input, digit1, "a number").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 2));
+        assert_eq!(error.found, "x");
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "32T3K 765\nT55X5 684";
+        let error = ParseError::new(input, &input[13..], "a card").in_file(Path::new("d7.txt"));
+        assert_eq!(
+            error.to_string(),
+            "d7.txt:2:4: expected a card, found \"X5 684\"\n    T55X5 684\n       ^"
+        );
+    }
+}
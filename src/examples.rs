@@ -0,0 +1,88 @@
+use crate::context::Context;
+use crate::runner;
+use crate::solution::Runner;
+use crate::verify;
+
+/// Runs every registered example of the given days and compares the answers.
+/// Returns false if any answer differs or any example failed to run.
+pub fn check(solvers: &[&dyn Runner], ctx: &Context) -> bool {
+    let mut ok = true;
+    for solver in solvers {
+        if solver.examples().is_empty() {
+            println!("{:<10} NONE", solver.name());
+            continue;
+        }
+        for (i, example) in solver.examples().iter().enumerate() {
+            let label = format!("{} #{}", solver.name(), i + 1);
+            if example.part1.is_none() && example.part2.is_none() {
+                println!("{:<10} SKIP    no expected answers yet", label);
+                continue;
+            }
+            let result = match runner::run_input(
+                *solver,
+                example.input,
+                example.parts(),
+                ctx,
+                &mut Vec::new(),
+            ) {
+                Ok(result) => result,
+                Err(e) => {
+                    println!("{:<10} ERROR   {:#}", label, e);
+                    ok = false;
+                    continue;
+                }
+            };
+            for (phase, outcome) in result.outcomes() {
+                let label = format!("{}p{} #{}", solver.name(), phase, i + 1);
+                let actual = match outcome {
+                    Ok(actual) => actual,
+                    Err(e) => {
+                        println!("{:<10} ERROR   {:#}", label, e);
+                        ok = false;
+                        continue;
+                    }
+                };
+                if let Some(want) = example.expected(phase) {
+                    ok &= verify::compare(&label, want, actual);
+                }
+            }
+        }
+    }
+    ok
+}
+
+/// Asserts every registered example gives its expected answers once `transform` is applied to
+/// its input
+#[cfg(test)]
+pub(crate) fn assert_registered_examples(transform: impl Fn(&str) -> String) {
+    for solver in crate::registry::SOLVERS {
+        for example in solver.examples() {
+            let result = runner::run_input(
+                *solver,
+                &transform(example.input),
+                example.parts(),
+                &Context::default(),
+                &mut Vec::new(),
+            )
+            .unwrap_or_else(|e| panic!("{}: {:#}", solver.name(), e));
+            if let Some((phase, e)) = result.errors().first() {
+                panic!("{}p{}: {:#}", solver.name(), phase, e);
+            }
+            for (phase, actual) in result.parts() {
+                if let Some(want) = example.expected(phase) {
+                    assert!(actual.matches(want), "{}p{}", solver.name(), phase);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_examples() {
+        assert_registered_examples(str::to_string);
+    }
+}
@@ -0,0 +1,281 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+
+/// A cell position as `(x, y)`, `x` counting columns from the left and `y` rows from the top
+pub type Pos = (usize, usize);
+
+/// A step between positions as `(dx, dy)`
+pub type Offset = (isize, isize);
+
+const NEIGHBOURS4: [Offset; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const NEIGHBOURS8: [Offset; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A dense rectangular grid, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid by calling `f` on every position, row by row
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a map of characters, one row per line, turning each character into a cell with `f`.
+    /// Characters `f` returns `None` for and rows of a different width are errors, `expected`
+    /// describes what the map should look like.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.lines() {
+            let mut columns = 0;
+            for (i, c) in line.char_indices() {
+                let cell = f(c).ok_or_else(|| ParseError::new(input, &line[i..], expected))?;
+                if width.is_some_and(|width| columns == width) {
+                    return Err(ParseError::new(input, &line[i..], "the end of the row"));
+                }
+                cells.push(cell);
+                columns += 1;
+            }
+            match width {
+                Some(width) if columns < width => {
+                    let expected = format!("a row of {} cells", width);
+                    return Err(ParseError::new(input, &line[line.len()..], expected));
+                }
+                Some(_) => {}
+                None => width = Some(columns),
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        match x < self.width && y < self.height {
+            true => self.cells.get(y * self.width + x),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Pos) -> Option<&mut T> {
+        match x < self.width && y < self.height {
+            true => self.cells.get_mut(y * self.width + x),
+            false => None,
+        }
+    }
+
+    /// `pos` moved by `offset`, `None` if that leaves the grid
+    pub fn offset(&self, (x, y): Pos, (dx, dy): Offset) -> Option<Pos> {
+        let x = x.checked_add_signed(dx).filter(|x| *x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|y| *y < self.height)?;
+        Some((x, y))
+    }
+
+    /// The positions above, right of, below and left of `pos` that are on the grid
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS4
+            .iter()
+            .filter_map(move |offset| self.offset(pos, *offset))
+    }
+
+    /// The positions around `pos`, diagonals included, that are on the grid
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS8
+            .iter()
+            .filter_map(move |offset| self.offset(pos, *offset))
+    }
+
+    /// Every position with its cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swaps rows and columns, mirroring the grid along its main diagonal
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// Turns the grid a quarter turn clockwise
+    pub fn rotate(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Mirrors the grid left to right
+    pub fn flip_horizontal(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Mirrors the grid top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            self[(x, self.height - 1 - y)].clone()
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", pos))
+    }
+}
+
+/// Writes the cells back as a map, one line per row
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(input: &str) -> Grid<char> {
+        Grid::parse(input, "anything", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = chars("ab\ncd\nef");
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.get((1, 2)), Some(&'f'));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), "ab\ncd\nef");
+
+        let digits = |c: char| c.to_digit(10);
+        let error = Grid::parse("12\n3x", "digits", digits).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (2, 2, "x")
+        );
+        let error = Grid::parse("12\n3", "digits", digits).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "a row of 2 cells");
+        let error = Grid::parse("12\n345", "digits", digits).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = chars("abc\ndef\nghi");
+        let at = |positions: Vec<Pos>| -> String { positions.iter().map(|p| grid[*p]).collect() };
+        assert_eq!(at(grid.neighbours4((1, 1)).collect()), "bfhd");
+        assert_eq!(at(grid.neighbours4((0, 0)).collect()), "bd");
+        assert_eq!(at(grid.neighbours8((1, 1)).collect()), "abcfihgd");
+        assert_eq!(at(grid.neighbours8((2, 2)).collect()), "efh");
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (2, 1)), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = chars("abc\ndef");
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'e')));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = chars("abc\ndef");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate().to_string(), "da\neb\nfc");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+
+/// A puzzle input cleaned up by [`normalize`]
+#[derive(Debug, PartialEq)]
+pub struct Normalized<'a> {
+    pub text: Cow<'a, str>,
+    /// Blank lines dropped from the start, to keep reported line numbers true to the file
+    pub leading_lines: usize,
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Strips a byte order mark, turns CRLF into LF and drops trailing blank lines along with the
+/// final line break. Leading blank lines are dropped too if `trim_leading` is set.
+pub fn normalize(input: &str, trim_leading: bool) -> Normalized<'_> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let text: Cow<str> = match input.contains('\r') {
+        true => input.replace("\r\n", "\n").into(),
+        false => input.into(),
+    };
+
+    let mut end = text.len();
+    while let Some(i) = text[..end].rfind('\n') {
+        if !is_blank(&text[i + 1..end]) {
+            break;
+        }
+        end = i;
+    }
+    if is_blank(&text[..end]) {
+        end = 0;
+    }
+
+    let mut start = 0;
+    let mut leading_lines = 0;
+    if trim_leading {
+        while let Some(i) = text[start..end].find('\n') {
+            if !is_blank(&text[start..start + i]) {
+                break;
+            }
+            start += i + 1;
+            leading_lines += 1;
+        }
+    }
+
+    let text = match text {
+        Cow::Borrowed(text) => Cow::Borrowed(&text[start..end]),
+        Cow::Owned(text) if start == 0 && end == text.len() => Cow::Owned(text),
+        Cow::Owned(text) => Cow::Owned(text[start..end].to_string()),
+    };
+    Normalized {
+        text,
+        leading_lines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::assert_registered_examples;
+
+    fn text(input: &str, trim_leading: bool) -> String {
+        normalize(input, trim_leading).text.into_owned()
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(text("1\n2\n", false), "1\n2");
+        assert_eq!(text("\u{feff}1\r\n2\r\n\r\n  \r\n", false), "1\n2");
+        assert_eq!(text("\n\n  1\n2", false), "\n\n  1\n2");
+        assert_eq!(text("\n \n  1\n2", true), "  1\n2");
+        assert_eq!(text("\n\n", true), "");
+        assert_eq!(text("", true), "");
+        assert_eq!(normalize("\r\n\r\n1", true).leading_lines, 2);
+        assert!(matches!(
+            normalize("1\n2\n", true).text,
+            Cow::Borrowed("1\n2")
+        ));
+    }
+
+    #[test]
+    fn test_every_day_accepts_windows_input() {
+        assert_registered_examples(|input| format!("\u{feff}{}\n\n", input).replace('\n', "\r\n"));
+    }
+}
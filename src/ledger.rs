@@ -0,0 +1,223 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::anyhow;
+use clap::ValueEnum;
+
+use log::warn;
+
+use crate::solution::{Answer, DayResult, Runner};
+
+/// What the puzzle site said about a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.to_possible_value().expect("no verdict is skipped");
+        write!(f, "{}", value.get_name())
+    }
+}
+
+/// One submitted answer, `key` is the output name like `d4p1`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub year: u16,
+    pub key: String,
+    pub verdict: Verdict,
+    pub answer: Answer,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.year, self.key, self.verdict, self.answer
+        )
+    }
+}
+
+/// Every answer submitted so far, in the order they were recorded
+#[derive(Debug, Default)]
+pub struct Ledger {
+    pub attempts: Vec<Attempt>,
+}
+
+/// One `<year> <output name> <verdict> <answer>` line per attempt, e.g. `2023 d4p1 too-high 30000`
+fn parse_ledger(content: &str) -> anyhow::Result<Ledger> {
+    let attempts = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            let [year, key, verdict, answer] = fields[..] else {
+                return Err(anyhow!("line {}: expected 4 fields", i + 1));
+            };
+            Ok(Attempt {
+                year: year
+                    .parse()
+                    .map_err(|e| anyhow!("line {}: {:?} is not a year: {}", i + 1, year, e))?,
+                key: key.to_string(),
+                verdict: Verdict::from_str(verdict, false)
+                    .map_err(|_| anyhow!("line {}: unknown verdict {:?}", i + 1, verdict))?,
+                answer: answer.parse().unwrap(),
+            })
+        })
+        .collect::<anyhow::Result<_>>()?;
+    Ok(Ledger { attempts })
+}
+
+/// Reads the ledger at `path`, a missing file is an empty ledger
+pub fn load(path: &Path) -> anyhow::Result<Ledger> {
+    if !path.exists() {
+        return Ok(Ledger::default());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow!("Unable to read {:?}: {}", path, e))?;
+    parse_ledger(&content).map_err(|e| anyhow!("{:?}: {}", path, e))
+}
+
+/// Warns about answers in `output` the ledger at `path` already rules out
+pub fn check(path: &Path, solver: &dyn Runner, output: &DayResult) {
+    match load(path) {
+        Ok(ledger) => {
+            for (key, answer) in output.answers(solver.name()) {
+                for warning in ledger.warnings(solver.year(), &key, answer) {
+                    warn!("{}", warning);
+                }
+            }
+        }
+        Err(e) => warn!("Ledger not checked: {:#}", e),
+    }
+}
+
+/// Appends `attempt` to the ledger at `path`, creating it if needed
+pub fn record(path: &Path, attempt: &Attempt) -> anyhow::Result<()> {
+    if attempt.answer.to_string().contains('\n') {
+        return Err(anyhow!("multi-line answers can't be recorded"));
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| anyhow!("Unable to open {:?}: {}", path, e))?;
+    writeln!(file, "{}", attempt)?;
+    Ok(())
+}
+
+impl Ledger {
+    /// Reasons to doubt `answer` for `key`: it was rejected before, it is outside the bounds the
+    /// too high and too low verdicts left, or it differs from the answer known to be correct
+    pub fn warnings(&self, year: u16, key: &str, answer: &Answer) -> Vec<String> {
+        let attempts: Vec<&Attempt> = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.year == year && attempt.key == key)
+            .collect();
+        let mut warnings = Vec::new();
+        if let Some(correct) = attempts
+            .iter()
+            .find(|attempt| attempt.verdict == Verdict::Correct)
+        {
+            if correct.answer != *answer {
+                warnings.push(format!(
+                    "{} = {} but {} was accepted",
+                    key, answer, correct.answer
+                ));
+            }
+            return warnings;
+        }
+        if let Some(rejected) = attempts.iter().find(|attempt| attempt.answer == *answer) {
+            warnings.push(format!(
+                "{} = {} was already rejected as {}",
+                key, answer, rejected.verdict
+            ));
+            return warnings;
+        }
+        let bound = |verdict: Verdict| {
+            attempts
+                .iter()
+                .filter(move |attempt| attempt.verdict == verdict)
+                .map(|attempt| &attempt.answer)
+        };
+        if let Some(high) = bound(Verdict::TooHigh).find(|high| answer >= *high) {
+            warnings.push(format!(
+                "{} = {} but {} was already too high",
+                key, answer, high
+            ));
+        }
+        if let Some(low) = bound(Verdict::TooLow).find(|low| answer <= *low) {
+            warnings.push(format!(
+                "{} = {} but {} was already too low",
+                key, answer, low
+            ));
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEDGER: &str = "\
+# submitted by hand
+2023 d4p1 too-high 30000
+2023 d4p1 too-low 100
+2023 d4p1 wrong 24000
+2023 d4p2 correct 8736438
+2022 d4p1 wrong 500
+";
+
+    #[test]
+    fn test_parse_ledger() {
+        let ledger = parse_ledger(LEDGER).unwrap();
+        assert_eq!(ledger.attempts.len(), 5);
+        assert_eq!(
+            ledger.attempts[0],
+            Attempt {
+                year: 2023,
+                key: "d4p1".to_string(),
+                verdict: Verdict::TooHigh,
+                answer: Answer::Unsigned(30000),
+            }
+        );
+        assert_eq!(ledger.attempts[0].to_string(), "2023 d4p1 too-high 30000");
+        assert!(parse_ledger("2023 d4p1 maybe 1").is_err());
+        assert!(parse_ledger("2023 d4p1").is_err());
+    }
+
+    #[test]
+    fn test_warnings() {
+        let ledger = parse_ledger(LEDGER).unwrap();
+        let warnings = |key: &str, answer: u64| ledger.warnings(2023, key, &Answer::from(answer));
+        assert!(warnings("d4p1", 24542).is_empty());
+        assert!(warnings("d4p1", 500).is_empty());
+        assert_eq!(
+            warnings("d4p1", 24000),
+            ["d4p1 = 24000 was already rejected as wrong"]
+        );
+        assert_eq!(
+            warnings("d4p1", 30001),
+            ["d4p1 = 30001 but 30000 was already too high"]
+        );
+        assert_eq!(
+            warnings("d4p1", 99),
+            ["d4p1 = 99 but 100 was already too low"]
+        );
+        assert!(warnings("d4p2", 8736438).is_empty());
+        assert_eq!(warnings("d4p2", 1), ["d4p2 = 1 but 8736438 was accepted"]);
+        assert!(ledger
+            .warnings(2023, "d4p1", &Answer::from("text"))
+            .is_empty());
+    }
+}
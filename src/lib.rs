@@ -0,0 +1,19 @@
+#![feature(btree_cursors)]
+
+pub mod bench;
+pub mod config;
+pub mod context;
+pub mod error;
+pub mod examples;
+pub mod grid;
+pub mod input;
+pub mod ledger;
+pub mod memory;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod verify;
+pub mod watch;
+pub mod y2023;
@@ -1,76 +1,277 @@
-#![feature(btree_cursors)]
-
 use std::fs;
-use std::path::Path;
-use std::str::from_utf8;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use clap::Parser;
+use anyhow::anyhow;
+use aoc_2023::config::Config;
+use aoc_2023::context::Context;
+use aoc_2023::ledger::{self, Attempt};
+use aoc_2023::report::{self, Format};
+use aoc_2023::solution::{DayResult, Runner};
+use aoc_2023::{bench, examples, registry, runner, scaffold, verify, watch};
+use clap::CommandFactory;
+use log::info;
 
-use crate::cli::Args;
-use crate::solution::Day;
-use crate::DayResult::{FullSolution, SinglePart};
+use crate::cli::{Args, Command};
 
 mod cli;
-mod day1;
-mod day10;
-mod day11;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day7;
-mod day8;
-mod day9;
-mod solution;
 
-enum DayResult {
-    SinglePart(String),
-    FullSolution(solution::Solution),
-}
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOC: aoc_2023::memory::CountingAlloc = aoc_2023::memory::CountingAlloc;
 
-fn main() {
-    let args = Args::parse();
+fn main() -> ExitCode {
+    #[cfg(feature = "alloc-stats")]
+    aoc_2023::memory::enable();
+    let args = Args::try_parse_args(std::env::args_os()).unwrap_or_else(|e| e.exit());
+    env_logger::Builder::new()
+        .filter_level(args.log_level())
+        .format_timestamp(None)
+        .format_target(false)
+        .parse_default_env()
+        .init();
 
-    let infile_name = format!("{:?}.txt", &args.solution).to_lowercase();
-    let infile = Path::new("./inputs/").join(infile_name);
-    println!("Reading input from: {:?}", &infile);
-    let bytes = fs::read(infile).expect("Unable to read file");
-    let input_content = from_utf8(&bytes).expect("Unable to parse file");
+    match dispatch(args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
 
-    println!("Running solution: {:?}", &args.solution);
-    let output = match args.solution {
-        Day::D1P1 => SinglePart(day1::part1::run(input_content).unwrap()),
-        Day::D1P2 => SinglePart(day1::part2::run(input_content).unwrap()),
-        Day::D2P1 => SinglePart(day2::part1::run(input_content).unwrap()),
-        Day::D2P2 => SinglePart(day2::part2::run(input_content).unwrap()),
-        Day::D3P1 => SinglePart(day3::part1::run(input_content).unwrap()),
-        Day::D3P2 => SinglePart(day3::part2::run(input_content).unwrap()),
-        Day::D4 => FullSolution(day4::run(input_content).unwrap()),
-        Day::D5 => FullSolution(day5::run(input_content).unwrap()),
-        Day::D7 => FullSolution(day7::run(input_content).unwrap()),
-        Day::D8 => FullSolution(day8::run(input_content).unwrap()),
-        Day::D9 => FullSolution(day9::run(input_content).unwrap()),
-        Day::D10 => FullSolution(day10::run(input_content).unwrap()),
-        Day::D11 => FullSolution(day11::run(input_content).unwrap()),
-    };
-    match output {
-        SinglePart(output) => {
-            let outfile_name = format!("{:?}.txt", &args.solution).to_lowercase();
-            let outfile = Path::new("./outputs/").join(outfile_name);
-            fs::write(&outfile, output).expect("Unable to write file");
-            println!("Output written to: {:?}", &outfile)
+fn dispatch(args: Args) -> anyhow::Result<ExitCode> {
+    let config = args.config()?;
+    let interactive = io::stdout().is_terminal() && io::stderr().is_terminal();
+    let ctx = Context::new(interactive && !args.quiet)
+        .with_timeout(config.timeout)
+        .with_params(config.params.clone());
+    Ok(match args.command {
+        Some(Command::RunAll { jobs }) => {
+            if runner::run_all(
+                &config.inputs_dir,
+                &args.ledger,
+                config.year,
+                config.format,
+                jobs as usize,
+                &ctx,
+            )? {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Some(Command::Verify { solutions, answers }) => {
+            let expected = verify::load_expected(&answers)?;
+            if verify::verify(
+                &selected(config.year, &solutions)?,
+                &config.inputs_dir,
+                &expected,
+                &ctx,
+            ) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Some(Command::Examples { solutions }) => {
+            if examples::check(&selected(config.year, &solutions)?, &ctx) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Some(Command::Bench {
+            solutions,
+            runs,
+            warmup,
+            baseline,
+            save_baseline,
+        }) => {
+            let baseline = baseline
+                .map(|path| bench::load_baseline(&path))
+                .transpose()?;
+            let mut reports = Vec::new();
+            for name in &solutions {
+                let solver = find(config.year, name)?;
+                let input = runner::read_input(&runner::input_path(&config.inputs_dir, solver))?;
+                let report = bench::bench(
+                    solver,
+                    &input,
+                    config.timeout,
+                    &config.params,
+                    warmup as usize,
+                    runs as usize,
+                )
+                .map_err(|e| anyhow!("{}: {:#}", name, e))?;
+                reports.push(report);
+            }
+            let regressions = bench::print_reports(&reports, baseline.as_ref());
+            if let Some(path) = save_baseline {
+                bench::save_baseline(&path, &reports)?;
+                info!("Baseline saved to: {:?}", &path);
+            }
+            if regressions == 0 {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Some(Command::Record {
+            solution,
+            part,
+            verdict,
+            answer,
+        }) => {
+            let solver = find(config.year, &solution)?;
+            let key = format!("{}p{}", solver.name(), part);
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let outfile = outputs_dir(&config, solver.year()).join(format!("{}.txt", key));
+                    fs::read_to_string(&outfile).map_err(|e| {
+                        anyhow!("No answer given and unable to read {:?}: {}", outfile, e)
+                    })?
+                }
+            };
+            let attempt = Attempt {
+                year: solver.year(),
+                key,
+                verdict,
+                answer: answer.parse()?,
+            };
+            ledger::record(&args.ledger, &attempt)?;
+            println!("Recorded {}", attempt);
+            ExitCode::SUCCESS
         }
-        FullSolution(solution) => {
-            println!("Part 1: {}", solution.part1);
-            println!("Part 2: {}", solution.part2);
-            let p1_outfile_name = format!("{:?}p1.txt", &args.solution).to_lowercase();
-            let p1_outfile = Path::new("./outputs/").join(p1_outfile_name);
-            fs::write(&p1_outfile, solution.part1).expect("Unable to write file");
-            println!("Part 1 output written to: {:?}", &p1_outfile);
-            let p2_outfile_name = format!("{:?}p2.txt", &args.solution).to_lowercase();
-            let p2_outfile = Path::new("./outputs/").join(p2_outfile_name);
-            fs::write(&p2_outfile, solution.part2).expect("Unable to write file");
-            println!("Part 2 output written to: {:?}", &p2_outfile);
+        Some(Command::NewDay { day }) => {
+            for path in scaffold::new_day(Path::new("."), &config.inputs_dir, config.year, day)? {
+                println!("Wrote {:?}", path);
+            }
+            ExitCode::SUCCESS
+        }
+        Some(Command::Completions { shell }) => {
+            let mut command = Args::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+            ExitCode::SUCCESS
+        }
+        Some(Command::Manpage { out_dir }) => {
+            match out_dir {
+                Some(dir) => {
+                    fs::create_dir_all(&dir)?;
+                    clap_mangen::generate_to(Args::command(), &dir)?;
+                    info!("Man pages written to: {:?}", &dir);
+                }
+                None => clap_mangen::Man::new(Args::command()).render(&mut io::stdout())?,
+            }
+            ExitCode::SUCCESS
+        }
+        None => {
+            let name = args
+                .solution
+                .as_deref()
+                .expect("required unless a subcommand is given");
+            run_one(&args, &config, name, &ctx)?
+        }
+    })
+}
+
+fn find(year: u16, name: &str) -> anyhow::Result<&'static dyn Runner> {
+    registry::find(year, name).ok_or(anyhow!("{} is not solved for {}", name, year))
+}
+
+/// The named days of `year`, or every day registered for it if none are named
+fn selected(year: u16, solutions: &[String]) -> anyhow::Result<Vec<&'static dyn Runner>> {
+    match solutions.is_empty() {
+        true => Ok(registry::for_year(year)),
+        false => solutions.iter().map(|name| find(year, name)).collect(),
+    }
+}
+
+fn run_one(args: &Args, config: &Config, name: &str, ctx: &Context) -> anyhow::Result<ExitCode> {
+    let solver = find(config.year, name)?;
+    let (parts, format) = (args.part, config.format);
+
+    let infile = args
+        .input
+        .clone()
+        .unwrap_or_else(|| runner::input_path(&config.inputs_dir, solver));
+    if args.watch {
+        watch::watch(solver, &infile, parts, &args.ledger, ctx)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    if format != Format::Text {
+        let mut timings = Vec::new();
+        let outcome = runner::solve(solver, &infile, parts, ctx, &mut timings);
+        report::print_records(&report::records(solver, &outcome, &timings), format)?;
+        return Ok(match outcome {
+            Ok(output) => {
+                ledger::check(&args.ledger, solver, &output);
+                write_outputs(config, solver, &output)?;
+                exit_code(&output)
+            }
+            Err(_) => ExitCode::FAILURE,
+        });
+    }
+
+    info!("Reading input from: {:?}", &infile);
+    info!(
+        "Running solution: {} (day {} of {})",
+        solver.name(),
+        solver.day(),
+        solver.year()
+    );
+    let output = runner::solve(solver, &infile, parts, ctx, &mut Vec::new())?;
+    for (phase, outcome) in output.outcomes() {
+        match outcome {
+            Ok(answer) => println!("Part {}: {}", phase, answer),
+            Err(e) => eprintln!("Part {}: error: {:#}", phase, e),
         }
     }
+    ledger::check(&args.ledger, solver, &output);
+    for outfile in write_outputs(config, solver, &output)? {
+        info!("Output written to: {:?}", &outfile);
+    }
+    Ok(exit_code(&output))
+}
+
+/// Fails when any part failed, even if the other one was answered
+fn exit_code(output: &DayResult) -> ExitCode {
+    match output.errors().is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+/// `<outputs dir>/<year>/`, the default year keeps using the outputs directory itself
+fn outputs_dir(config: &Config, year: u16) -> PathBuf {
+    match year == registry::DEFAULT_YEAR {
+        true => config.outputs_dir.clone(),
+        false => config.outputs_dir.join(year.to_string()),
+    }
+}
+
+/// Writes every answer to `<outputs dir>/<name>.txt` unless the config turned that off,
+/// returning the files written
+fn write_outputs(
+    config: &Config,
+    solver: &dyn Runner,
+    output: &DayResult,
+) -> anyhow::Result<Vec<PathBuf>> {
+    if !config.write_outputs {
+        return Ok(Vec::new());
+    }
+    let dir = outputs_dir(config, solver.year());
+    fs::create_dir_all(&dir)?;
+    output
+        .answers(solver.name())
+        .into_iter()
+        .map(|(stem, answer)| {
+            let outfile = dir.join(format!("{}.txt", stem));
+            fs::write(&outfile, answer.to_string())?;
+            Ok(outfile)
+        })
+        .collect()
 }
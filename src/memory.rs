@@ -0,0 +1,167 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// What one phase of a run allocated
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Usage {
+    /// Most bytes held at once, on top of what was held when the phase started
+    pub peak: usize,
+    pub allocations: usize,
+    /// Every byte allocated, including the ones freed again
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Counters {
+    current: usize,
+    peak: usize,
+    allocations: usize,
+    total: usize,
+}
+
+thread_local! {
+    // per thread so days running side by side with `--jobs` don't count each other's allocations
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            current: 0,
+            peak: 0,
+            allocations: 0,
+            total: 0,
+        })
+    };
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The system allocator, counting what every thread allocates once [`enable`] was called.
+/// Only installed as the `#[global_allocator]` with the `alloc-stats` feature.
+pub struct CountingAlloc;
+
+fn record(allocated: usize, freed: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // the counters are gone while the thread shuts down
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        // memory allocated before counting started or on another thread can be freed here
+        c.current = (c.current + allocated).saturating_sub(freed);
+        c.peak = c.peak.max(c.current);
+        if allocated > 0 {
+            c.allocations += 1;
+            c.total += allocated;
+        }
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Starts counting, this only measures anything when [`CountingAlloc`] is the global allocator
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` and returns what it allocated on this thread, `None` unless counting is enabled
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    let before = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        c.peak = c.current;
+        counters.set(c);
+        c
+    });
+    let result = f();
+    let after = COUNTERS.with(Cell::get);
+    let usage = Usage {
+        peak: after.peak - before.current,
+        allocations: after.allocations - before.allocations,
+        total: after.total - before.total,
+    };
+    (result, Some(usage))
+}
+
+/// `1536` is `1.5 KiB`
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", bytes),
+        _ => format!("{:.1} {}", size, UNITS[unit - 1]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn test_measure() {
+        enable();
+        let (_, usage) = measure(|| {
+            let mut grown: Vec<u8> = Vec::with_capacity(1000);
+            grown.resize(3000, 0);
+            let kept = vec![0u64; 100];
+            drop(grown);
+            kept
+        });
+        let usage = usage.unwrap();
+        assert!(usage.peak >= 3000 + 800, "{:?}", usage);
+        assert!(usage.allocations >= 3, "{:?}", usage);
+        assert!(usage.total >= 1000 + 3000 + 800, "{:?}", usage);
+
+        let (_, usage) = measure(|| 1 + 1);
+        assert_eq!(usage, Some(Usage::default()));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
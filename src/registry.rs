@@ -0,0 +1,67 @@
+use crate::solution::Runner;
+use crate::y2023;
+
+/// The year used when `--year` isn't given
+pub const DEFAULT_YEAR: u16 = 2023;
+
+/// Every solved day by year and day, the order they are listed on the command line
+pub static SOLVERS: &[&dyn Runner] = &[
+    &y2023::day1::Day1,
+    &y2023::day2::Day2,
+    &y2023::day3::Day3,
+    &y2023::day4::Day4,
+    &y2023::day5::Day5,
+    &y2023::day7::Day7,
+    &y2023::day8::Day8,
+    &y2023::day9::Day9,
+    &y2023::day10::Day10,
+    &y2023::day11::Day11,
+];
+
+/// Every day name used in any year, e.g. `d4`
+pub fn names() -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = Vec::new();
+    for solver in SOLVERS {
+        if !names.contains(&solver.name()) {
+            names.push(solver.name());
+        }
+    }
+    names
+}
+
+/// The days solved for `year`, in day order
+pub fn for_year(year: u16) -> Vec<&'static dyn Runner> {
+    SOLVERS
+        .iter()
+        .filter(|solver| solver.year() == year)
+        .copied()
+        .collect()
+}
+
+pub fn find(year: u16, name: &str) -> Option<&'static dyn Runner> {
+    SOLVERS
+        .iter()
+        .find(|solver| solver.year() == year && solver.name() == name)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solvers_are_ordered_by_year_and_day() {
+        let keys: Vec<(u16, u8)> = SOLVERS.iter().map(|s| (s.year(), s.day())).collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(DEFAULT_YEAR, "d4").map(|solver| solver.day()), Some(4));
+        assert!(find(DEFAULT_YEAR, "d6").is_none());
+        assert!(find(2015, "d4").is_none());
+    }
+}
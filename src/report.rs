@@ -0,0 +1,265 @@
+use std::fmt;
+use std::io::Write;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::context::TimedOut;
+use crate::memory::format_bytes;
+use crate::runner::print_table;
+use crate::solution::{Answer, DayResult, Phase, Runner, Timing};
+
+/// How run results are written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Status {
+    Ok,
+    Error,
+    Timeout,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "OK"),
+            Status::Error => write!(f, "ERROR"),
+            Status::Timeout => write!(f, "TIMEOUT"),
+        }
+    }
+}
+
+impl Serialize for Phase {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// One phase of a day's run, the stable schema behind `--format json|csv`
+#[derive(Debug, Clone, Serialize)]
+pub struct Record {
+    /// Solution name as given on the command line, e.g. `d4`
+    pub name: &'static str,
+    pub year: u16,
+    pub day: u8,
+    /// `parse`, `1` or `2`, missing when the day failed before any phase finished
+    pub part: Option<Phase>,
+    pub answer: Option<String>,
+    #[serde(rename = "duration_ms", serialize_with = "as_millis")]
+    pub duration: Option<Duration>,
+    pub status: Status,
+    pub error: Option<String>,
+    /// The allocation columns are only filled in with the `alloc-stats` feature
+    pub peak_bytes: Option<usize>,
+    pub allocations: Option<usize>,
+    pub allocated_bytes: Option<usize>,
+}
+
+fn as_millis<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    duration
+        .map(|duration| duration.as_secs_f64() * 1000.0)
+        .serialize(serializer)
+}
+
+fn error_status(e: &anyhow::Error) -> Status {
+    match e.is::<TimedOut>() {
+        true => Status::Timeout,
+        false => Status::Error,
+    }
+}
+
+/// Turns the outcome of running `solver` into records, one for parsing and one per part that ran,
+/// each part with its own status. A day that failed before its parts ran is a single error record.
+pub fn records(
+    solver: &dyn Runner,
+    outcome: &anyhow::Result<DayResult>,
+    timings: &[Timing],
+) -> Vec<Record> {
+    let record = |phase: Phase, outcome: Result<Option<&Answer>, &anyhow::Error>| {
+        let timing = timings.iter().find(|timing| timing.phase == phase);
+        let memory = timing.and_then(|timing| timing.memory);
+        Record {
+            name: solver.name(),
+            year: solver.year(),
+            day: solver.day(),
+            part: Some(phase),
+            answer: outcome.ok().flatten().map(|answer| answer.to_string()),
+            duration: timing.map(|timing| timing.elapsed),
+            status: outcome.map_or_else(error_status, |_| Status::Ok),
+            error: outcome.err().map(|e| format!("{:#}", e)),
+            peak_bytes: memory.map(|memory| memory.peak),
+            allocations: memory.map(|memory| memory.allocations),
+            allocated_bytes: memory.map(|memory| memory.total),
+        }
+    };
+    match outcome {
+        Ok(result) => std::iter::once(record(Phase::Parse, Ok(None)))
+            .chain(
+                result
+                    .outcomes()
+                    .into_iter()
+                    .map(|(phase, outcome)| record(phase, outcome.map(Some))),
+            )
+            .collect(),
+        Err(e) => vec![Record {
+            name: solver.name(),
+            year: solver.year(),
+            day: solver.day(),
+            part: None,
+            answer: None,
+            duration: None,
+            status: error_status(e),
+            error: Some(format!("{:#}", e)),
+            peak_bytes: None,
+            allocations: None,
+            allocated_bytes: None,
+        }],
+    }
+}
+
+/// Writes `records` to stdout in the given format. Text is a summary table with errors on stderr.
+pub fn print_records(records: &[Record], format: Format) -> anyhow::Result<()> {
+    match format {
+        Format::Text => {
+            let with_memory = records.iter().any(|record| record.peak_bytes.is_some());
+            let rows: Vec<Vec<String>> = records
+                .iter()
+                .map(|record| {
+                    let mut row = vec![
+                        record.name.to_string(),
+                        record.part.map_or("-".to_string(), |part| part.to_string()),
+                        match record.status {
+                            Status::Ok => record.answer.clone().unwrap_or("-".to_string()),
+                            status => status.to_string(),
+                        },
+                        record
+                            .duration
+                            .map_or("-".to_string(), |d| format!("{:.2?}", d)),
+                    ];
+                    if with_memory {
+                        let bytes =
+                            |bytes: Option<usize>| bytes.map_or("-".to_string(), format_bytes);
+                        row.push(bytes(record.peak_bytes));
+                        row.push(
+                            record
+                                .allocations
+                                .map_or("-".to_string(), |n| n.to_string()),
+                        );
+                        row.push(bytes(record.allocated_bytes));
+                    }
+                    row
+                })
+                .collect();
+            let mut headers = vec!["day", "part", "answer", "time"];
+            if with_memory {
+                headers.extend(["peak", "allocs", "allocated"]);
+            }
+            print_table(&headers, &rows);
+            for record in records {
+                if let Some(error) = &record.error {
+                    match record.part {
+                        Some(part) => eprintln!("{} part {}: {}", record.name, part, error),
+                        None => eprintln!("{}: {}", record.name, error),
+                    }
+                }
+            }
+        }
+        Format::Json => {
+            let mut stdout = std::io::stdout().lock();
+            serde_json::to_writer_pretty(&mut stdout, records)?;
+            writeln!(stdout)?;
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(std::io::stdout().lock());
+            for record in records {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+    use crate::y2023::day4::Day4;
+
+    fn timing(phase: Phase, millis: u64) -> Timing {
+        Timing {
+            phase,
+            elapsed: Duration::from_millis(millis),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn test_records() {
+        let outcome = Ok(DayResult {
+            part1: Some(Ok(Answer::from(13u32))),
+            part2: Some(Ok(Answer::from(30u32))),
+        });
+        let timings = [
+            timing(Phase::Parse, 1),
+            timing(Phase::Part1, 2),
+            timing(Phase::Part2, 3),
+        ];
+        let records = records(&Day4, &outcome, &timings);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].part, Some(Phase::Part2));
+        assert_eq!(records[2].answer.as_deref(), Some("30"));
+        assert_eq!(records[2].duration, Some(Duration::from_millis(3)));
+        assert_eq!(
+            serde_json::to_string(&records[1]).unwrap(),
+            r#"{"name":"d4","year":2023,"day":4,"part":"1","answer":"13","duration_ms":2.0,"status":"OK","error":null,"peak_bytes":null,"allocations":null,"allocated_bytes":null}"#
+        );
+    }
+
+    #[test]
+    fn test_part_error_record() {
+        let outcome = Ok(DayResult {
+            part1: Some(Ok(Answer::from(13u32))),
+            part2: Some(Err(anyhow!("no start"))),
+        });
+        let records = records(&Day4, &outcome, &[]);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[1].status, Status::Ok);
+        assert_eq!(records[1].answer.as_deref(), Some("13"));
+        assert_eq!(records[2].part, Some(Phase::Part2));
+        assert_eq!(records[2].status, Status::Error);
+        assert_eq!(records[2].answer, None);
+        assert_eq!(records[2].error.as_deref(), Some("no start"));
+    }
+
+    #[test]
+    fn test_error_record() {
+        let records = records(&Day4, &Err(anyhow!("no input")), &[]);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].status, Status::Error);
+        assert_eq!(records[0].error.as_deref(), Some("no input"));
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.serialize(&records[0]).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+            "name,year,day,part,answer,duration_ms,status,error,peak_bytes,allocations,allocated_bytes\nd4,2023,4,,,,ERROR,no input,,,\n"
+        );
+    }
+
+    #[test]
+    fn test_timeout_record() {
+        let outcome = Err(TimedOut(Duration::from_secs(5)).into());
+        let records = records(&Day4, &outcome, &[]);
+        assert_eq!(records[0].status, Status::Timeout);
+        assert_eq!(records[0].error.as_deref(), Some("timed out after 5s"));
+    }
+}
@@ -0,0 +1,291 @@
+use std::any::Any;
+use std::fs;
+use std::io::{self, Read};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use anyhow::anyhow;
+
+use crate::context::Context;
+use crate::error::ParseError;
+use crate::ledger;
+use crate::registry;
+use crate::report::{self, print_records, Format, Status};
+use crate::solution::{DayResult, Parts, Runner, Timing};
+
+pub fn input_path(inputs_dir: &Path, solver: &dyn Runner) -> PathBuf {
+    day_input_path(inputs_dir, solver.year(), solver.name())
+}
+
+/// `<inputs_dir>/<year>/<name>.txt`, the default year falls back to the flat
+/// `<inputs_dir>/<name>.txt` layout from before there were years
+pub fn day_input_path(inputs_dir: &Path, year: u16, name: &str) -> PathBuf {
+    let file = format!("{}.txt", name);
+    let path = inputs_dir.join(year.to_string()).join(&file);
+    let flat = inputs_dir.join(&file);
+    match year == registry::DEFAULT_YEAR && !path.exists() && flat.exists() {
+        true => flat,
+        false => path,
+    }
+}
+
+/// Reads a puzzle input from `infile`, or from stdin when it is `-`
+pub fn read_input(infile: &Path) -> anyhow::Result<String> {
+    let bytes = if infile == Path::new("-") {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        bytes
+    } else {
+        fs::read(infile).map_err(|e| anyhow!("Unable to read {:?}: {}", infile, e))?
+    };
+    Ok(String::from_utf8(bytes)?)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// Runs the day on an in-memory input under the context's timeout, turning a panic into an error
+pub fn run_input(
+    solver: &dyn Runner,
+    input: &str,
+    parts: Parts,
+    ctx: &Context,
+    timings: &mut Vec<Timing>,
+) -> anyhow::Result<DayResult> {
+    let (ctx, _deadline) = ctx.start();
+    catch_unwind(AssertUnwindSafe(|| solver.run(input, parts, &ctx, timings)))
+        .unwrap_or_else(|payload| Err(anyhow!("panicked: {}", panic_message(payload))))
+}
+
+/// Reads the day's input and runs it, turning a panic into an error.
+/// Parse errors are tagged with the input file they came from.
+pub fn solve(
+    solver: &dyn Runner,
+    infile: &Path,
+    parts: Parts,
+    ctx: &Context,
+    timings: &mut Vec<Timing>,
+) -> anyhow::Result<DayResult> {
+    read_input(infile)
+        .and_then(|input| run_input(solver, &input, parts, ctx, timings))
+        .map_err(|e| match e.downcast::<ParseError>() {
+            Ok(parse_error) => parse_error.in_file(infile).into(),
+            Err(e) => e,
+        })
+}
+
+/// Prints left aligned columns separated by `|`, sized to fit the widest cell
+pub fn print_table<S: AsRef<str>>(headers: &[&str], rows: &[Vec<S>]) {
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].as_ref().len())
+                .chain([headers[i].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(text, width)| format!("{:<width$}", text, width = width))
+            .collect();
+        println!("{}", padded.join(" | ").trim_end());
+    };
+    line(headers.to_vec());
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    for row in rows {
+        line(row.iter().map(AsRef::as_ref).collect());
+    }
+}
+
+/// Reads and runs each of `solvers`, `jobs` of them at a time on their own threads.
+/// The outcomes and timings come back in the order of `solvers`, whatever order they finish in.
+pub fn solve_all(
+    solvers: &[&dyn Runner],
+    inputs_dir: &Path,
+    jobs: usize,
+    ctx: &Context,
+) -> Vec<(anyhow::Result<DayResult>, Vec<Timing>)> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, solvers.len().max(1)) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(solver) = solvers.get(index) else {
+                    break;
+                };
+                let mut timings = Vec::new();
+                let outcome = solve(
+                    *solver,
+                    &input_path(inputs_dir, *solver),
+                    Parts::Both,
+                    ctx,
+                    &mut timings,
+                );
+                sender.send((index, outcome, timings)).unwrap();
+            });
+        }
+    });
+    drop(sender);
+    let mut outcomes: Vec<_> = receiver.into_iter().collect();
+    outcomes.sort_by_key(|(index, _, _)| *index);
+    outcomes
+        .into_iter()
+        .map(|(_, outcome, timings)| (outcome, timings))
+        .collect()
+}
+
+/// Runs every day registered for `year`, `jobs` at a time, and prints the results in `format` in
+/// day order, warning about answers `ledger` rules out. Returns false if any day failed to read its
+/// input, errored or panicked.
+pub fn run_all(
+    inputs_dir: &Path,
+    ledger: &Path,
+    year: u16,
+    format: Format,
+    jobs: usize,
+    ctx: &Context,
+) -> anyhow::Result<bool> {
+    let solvers = registry::for_year(year);
+    if solvers.is_empty() {
+        return Err(anyhow!("No days are registered for {}", year));
+    }
+    let mut records = Vec::new();
+    let outcomes = solve_all(&solvers, inputs_dir, jobs, ctx);
+    for (solver, (outcome, timings)) in solvers.iter().zip(&outcomes) {
+        if let Ok(output) = outcome {
+            ledger::check(ledger, *solver, output);
+        }
+        records.extend(report::records(*solver, outcome, timings));
+    }
+    print_records(&records, format)?;
+    Ok(records.iter().all(|record| record.status == Status::Ok))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::solution::Answer;
+
+    /// Sleeps `delay_ms` and answers its day, or panics when `delay_ms` is 0
+    struct Fake {
+        name: &'static str,
+        delay_ms: u64,
+    }
+
+    impl Runner for Fake {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn year(&self) -> u16 {
+            0
+        }
+
+        fn day(&self) -> u8 {
+            0
+        }
+
+        fn examples(&self) -> &'static [crate::solution::Example] {
+            &[]
+        }
+
+        fn params(&self) -> &'static [crate::solution::Param] {
+            &[]
+        }
+
+        fn run(
+            &self,
+            _input: &str,
+            _parts: Parts,
+            _ctx: &Context,
+            _timings: &mut Vec<Timing>,
+        ) -> anyhow::Result<DayResult> {
+            if self.delay_ms == 0 {
+                panic!("{} gave up", self.name);
+            }
+            thread::sleep(Duration::from_millis(self.delay_ms));
+            Ok(DayResult {
+                part1: Some(Ok(Answer::from(self.name))),
+                part2: None,
+            })
+        }
+    }
+
+    #[test]
+    fn test_solve_all_keeps_order_and_isolates_panics() {
+        let dir = std::env::temp_dir().join(format!("aoc-solve-all-{}", std::process::id()));
+        let fakes = [
+            Fake {
+                name: "slow",
+                delay_ms: 50,
+            },
+            Fake {
+                name: "boom",
+                delay_ms: 0,
+            },
+            Fake {
+                name: "fast",
+                delay_ms: 1,
+            },
+        ];
+        fs::create_dir_all(dir.join("0")).unwrap();
+        for fake in &fakes {
+            fs::write(input_path(&dir, fake), "").unwrap();
+        }
+        let solvers: Vec<&dyn Runner> = fakes.iter().map(|fake| fake as &dyn Runner).collect();
+
+        for jobs in [1, 3] {
+            let outcomes: Vec<String> = solve_all(&solvers, &dir, jobs, &Context::default())
+                .into_iter()
+                .map(|(outcome, _)| match outcome {
+                    Ok(result) => result.parts()[0].1.to_string(),
+                    Err(e) => e.to_string(),
+                })
+                .collect();
+            assert_eq!(outcomes, ["slow", "panicked: boom gave up", "fast"]);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_input_path() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-path-{}", std::process::id()));
+        let day4 = registry::find(registry::DEFAULT_YEAR, "d4").unwrap();
+        let by_year = dir.join(registry::DEFAULT_YEAR.to_string()).join("d4.txt");
+        assert_eq!(input_path(&dir, day4), by_year);
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("d4.txt"), "").unwrap();
+        assert_eq!(input_path(&dir, day4), dir.join("d4.txt"));
+
+        fs::create_dir_all(by_year.parent().unwrap()).unwrap();
+        fs::write(&by_year, "").unwrap();
+        assert_eq!(input_path(&dir, day4), by_year);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
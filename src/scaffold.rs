@@ -0,0 +1,247 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+
+use crate::{registry, runner};
+
+/// Skeleton of a new day, `__YEAR__` and `__DAY__` stand for the year and day number
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// Adds `pub mod <name>;` to a module list like lib.rs, keeping the declarations sorted
+fn register_module(modules: &str, name: &str) -> anyhow::Result<String> {
+    let module = format!("pub mod {};", name);
+    let mut lines: Vec<&str> = modules.lines().collect();
+    if lines.contains(&module.as_str()) {
+        return Err(anyhow!("{} is already declared", name));
+    }
+    fn declared(line: &str) -> Option<&str> {
+        line.strip_prefix("pub mod ")?.strip_suffix(';')
+    }
+    let position = lines
+        .iter()
+        .position(|line| declared(line).is_some_and(|declared| declared > name))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| line.starts_with("pub mod "))
+                .map(|i| i + 1)
+        })
+        .ok_or(anyhow!("no `pub mod` declarations found"))?;
+    lines.insert(position, &module);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Imports the year's module in the registry and adds `&yYYYY::dayN::DayN` to `SOLVERS`,
+/// ordered by year and day
+fn register_solver(registry: &str, year: u16, day: u8) -> anyhow::Result<String> {
+    let entry = format!("&y{}::day{}::Day{},", year, day, day);
+    let mut lines: Vec<String> = registry.lines().map(str::to_string).collect();
+    if lines.iter().any(|line| line.trim() == entry) {
+        return Err(anyhow!("day{} of {} is already registered", day, year));
+    }
+
+    let import = lines
+        .iter_mut()
+        .find(|line| line.starts_with("use crate::y") || line.starts_with("use crate::{y"))
+        .ok_or(anyhow!(
+            "no `use crate::yYYYY` import found in the registry"
+        ))?;
+    let mut years: Vec<String> = import
+        .trim_start_matches("use crate::")
+        .trim_end_matches(';')
+        .trim_matches(|c| c == '{' || c == '}')
+        .split(", ")
+        .map(str::to_string)
+        .chain([format!("y{}", year)])
+        .collect();
+    years.sort();
+    years.dedup();
+    *import = match &years[..] {
+        [year] => format!("use crate::{};", year),
+        years => format!("use crate::{{{}}};", years.join(", ")),
+    };
+
+    let start = lines
+        .iter()
+        .position(|line| line.contains("SOLVERS"))
+        .ok_or(anyhow!("no `SOLVERS` list found in the registry"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "];")
+            .ok_or(anyhow!("unterminated `SOLVERS` list in the registry"))?;
+    let key_of = |line: &str| -> Option<(u16, u8)> {
+        let (year, rest) = line.trim().strip_prefix("&y")?.split_once("::day")?;
+        Some((year.parse().ok()?, rest[..rest.find("::")?].parse().ok()?))
+    };
+    let position = (start + 1..end)
+        .find(|i| key_of(&lines[*i]).is_some_and(|existing| existing > (year, day)))
+        .unwrap_or(end);
+    lines.insert(position, format!("    {}", entry));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Where a new day's input goes: the default year keeps the flat `<inputs_dir>/<name>.txt` layout
+/// while its inputs are still flat, otherwise `<inputs_dir>/<year>/<name>.txt`
+fn input_path(inputs_dir: &Path, year: u16, name: &str) -> PathBuf {
+    let path = runner::day_input_path(inputs_dir, year, name);
+    let flat_inputs = fs::read_dir(inputs_dir).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|entry| entry.path().extension().is_some_and(|ext| ext == "txt"))
+    });
+    match year == registry::DEFAULT_YEAR
+        && !path.exists()
+        && !inputs_dir.join(year.to_string()).exists()
+        && flat_inputs
+    {
+        true => inputs_dir.join(format!("{}.txt", name)),
+        false => path,
+    }
+}
+
+/// Generates `src/yYYYY/dayN/mod.rs` under `root`, registers it and creates an empty input file.
+/// Refuses to touch anything if the day already exists. Returns the files created or changed.
+pub fn new_day(root: &Path, inputs_dir: &Path, year: u16, day: u8) -> anyhow::Result<Vec<PathBuf>> {
+    let year_dir = root.join("src").join(format!("y{}", year));
+    let module_dir = year_dir.join(format!("day{}", day));
+    if module_dir.exists() {
+        return Err(anyhow!(
+            "{:?} already exists, not overwriting it",
+            module_dir
+        ));
+    }
+    let lib_path = root.join("src/lib.rs");
+    let year_path = year_dir.join("mod.rs");
+    let registry_path = root.join("src/registry.rs");
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| {
+            anyhow!(
+                "Unable to read {:?}, run this from the repository root: {}",
+                path,
+                e
+            )
+        })
+    };
+    // edit everything in memory first so a failure leaves the tree untouched
+    let day_module = format!("day{}", day);
+    let (year_module, lib) = match year_path.exists() {
+        true => (register_module(&read(&year_path)?, &day_module)?, None),
+        false => (
+            format!("pub mod {};\n", day_module),
+            Some(register_module(&read(&lib_path)?, &format!("y{}", year))?),
+        ),
+    };
+    let registry = register_solver(&read(&registry_path)?, year, day)?;
+
+    let module_path = module_dir.join("mod.rs");
+    fs::create_dir_all(&module_dir)?;
+    fs::write(
+        &module_path,
+        TEMPLATE
+            .replace("__YEAR__", &year.to_string())
+            .replace("__DAY__", &day.to_string()),
+    )?;
+    fs::write(&year_path, year_module)?;
+    let mut written = vec![module_path, year_path];
+    if let Some(lib) = lib {
+        fs::write(&lib_path, lib)?;
+        written.push(lib_path);
+    }
+    fs::write(&registry_path, registry)?;
+    written.push(registry_path);
+
+    let input_path = input_path(inputs_dir, year, &format!("d{}", day));
+    if !input_path.exists() {
+        fs::create_dir_all(input_path.parent().unwrap_or(inputs_dir))?;
+        fs::write(&input_path, "")?;
+        written.push(input_path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str =
+        "#![feature(btree_cursors)]\n\npub mod error;\npub mod registry;\npub mod y2023;\n";
+    const REGISTRY: &str = "\
+use crate::solution::Runner;
+use crate::y2023;
+
+pub static SOLVERS: &[&dyn Runner] = &[
+    &y2023::day1::Day1,
+    &y2023::day2::Day2,
+    &y2023::day10::Day10,
+];
+";
+
+    #[test]
+    fn test_register_module() {
+        assert_eq!(
+            register_module(LIB, "y2024").unwrap(),
+            "#![feature(btree_cursors)]\n\npub mod error;\npub mod registry;\npub mod y2023;\npub mod y2024;\n"
+        );
+        assert_eq!(
+            register_module("pub mod day1;\npub mod day10;\npub mod day2;\n", "day12").unwrap(),
+            "pub mod day1;\npub mod day10;\npub mod day12;\npub mod day2;\n"
+        );
+        assert!(register_module(LIB, "y2023").is_err());
+    }
+
+    #[test]
+    fn test_register_solver() {
+        let registry = register_solver(REGISTRY, 2023, 3).unwrap();
+        assert!(registry.contains("use crate::y2023;"));
+        assert!(registry.contains(
+            "    &y2023::day2::Day2,\n    &y2023::day3::Day3,\n    &y2023::day10::Day10,\n"
+        ));
+
+        let registry = register_solver(REGISTRY, 2023, 12).unwrap();
+        assert!(registry.contains("    &y2023::day10::Day10,\n    &y2023::day12::Day12,\n];"));
+
+        let registry = register_solver(REGISTRY, 2024, 1).unwrap();
+        assert!(registry.contains("use crate::{y2023, y2024};"));
+        assert!(registry.contains("    &y2023::day10::Day10,\n    &y2024::day1::Day1,\n];"));
+        let registry = register_solver(&registry, 2015, 2).unwrap();
+        assert!(registry.contains("use crate::{y2015, y2023, y2024};"));
+        assert!(registry.contains("= &[\n    &y2015::day2::Day2,\n    &y2023::day1::Day1,"));
+
+        assert!(register_solver(REGISTRY, 2023, 10).is_err());
+    }
+
+    #[test]
+    fn test_input_path() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let year = registry::DEFAULT_YEAR;
+        assert_eq!(
+            input_path(&dir, year, "d12"),
+            dir.join(format!("{}/d12.txt", year))
+        );
+
+        fs::write(dir.join("d1.txt"), "").unwrap();
+        assert_eq!(input_path(&dir, year, "d12"), dir.join("d12.txt"));
+        assert_eq!(input_path(&dir, 2015, "d12"), dir.join("2015/d12.txt"));
+
+        fs::create_dir_all(dir.join(year.to_string())).unwrap();
+        assert_eq!(
+            input_path(&dir, year, "d12"),
+            dir.join(format!("{}/d12.txt", year))
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_template() {
+        let module = TEMPLATE
+            .replace("__YEAR__", "2024")
+            .replace("__DAY__", "12");
+        assert!(module.contains("pub struct Day12;"));
+        assert!(module.contains("\"d12\""));
+        assert!(module.contains("        2024\n"));
+        assert!(!module.contains("__"));
+    }
+}
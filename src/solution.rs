@@ -1,25 +1,453 @@
-use clap::ValueEnum;
-
-/// AOC 2023 solutions
-#[derive(ValueEnum, Debug, Clone)]
-#[clap(rename_all = "kebab_case")]
-pub enum Day {
-    D1P1,
-    D1P2,
-    D2P1,
-    D2P2,
-    D3P1,
-    D3P2,
-    D4,
-    D5,
-    D7,
-    D8,
-    D9,
-    D10,
-    D11,
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use num::BigInt;
+
+use crate::context::Context;
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::memory::{self, Usage};
+
+/// A puzzle answer, integers compare by value regardless of which variant holds them and text
+/// compares with an integer by how the integer is written
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(BigInt),
+    Text(String),
+    Unsolved,
+}
+
+impl Answer {
+    fn as_big(&self) -> Option<BigInt> {
+        match self {
+            Answer::Signed(n) => Some(BigInt::from(*n)),
+            Answer::Unsigned(n) => Some(BigInt::from(*n)),
+            Answer::Big(n) => Some(n.clone()),
+            Answer::Text(_) | Answer::Unsolved => None,
+        }
+    }
+
+    /// Compares against an answer as written in an answers file, text exactly as written
+    pub fn matches(&self, expected: &str) -> bool {
+        match self {
+            Answer::Text(text) => text == expected.trim(),
+            _ => *self == expected.parse::<Answer>().unwrap(),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Text(text), other) | (other, Answer::Text(text)) => {
+                other.as_big().is_some() && *text == other.to_string()
+            }
+            (Answer::Unsolved, Answer::Unsolved) => true,
+            _ => match (self.as_big(), other.as_big()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Only integers are ordered, other answers are either equal or not comparable
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.as_big(), other.as_big()) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => (self == other).then_some(Ordering::Equal),
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self.matches(other)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+/// Inverse of `Display`: integers become the narrowest variant that holds them, anything else is text
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(if let Ok(n) = s.parse::<u64>() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse::<i64>() {
+            Answer::Signed(n)
+        } else if let Ok(n) = s.parse::<BigInt>() {
+            Answer::Big(n)
+        } else if s == "unsolved" {
+            Answer::Unsolved
+        } else {
+            Answer::Text(s.to_string())
+        })
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::$variant(n as $target)
+            }
+        })*
+    };
+}
+
+answer_from!(Unsigned, u64, u8, u16, u32, u64, usize);
+answer_from!(Signed, i64, i8, i16, i32, i64, isize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
 }
 
 pub struct Solution {
-    pub part1: String,
-    pub part2: String,
+    pub part1: Answer,
+    pub part2: Answer,
+}
+
+/// Outcomes of the parts that were run, a part that fails keeps the other part's answer
+#[derive(Default)]
+pub struct DayResult {
+    pub part1: Option<anyhow::Result<Answer>>,
+    pub part2: Option<anyhow::Result<Answer>>,
+}
+
+impl DayResult {
+    /// Every part that ran with its answer or error
+    pub fn outcomes(&self) -> Vec<(Phase, Result<&Answer, &anyhow::Error>)> {
+        [(Phase::Part1, &self.part1), (Phase::Part2, &self.part2)]
+            .into_iter()
+            .filter_map(|(phase, outcome)| Some((phase, outcome.as_ref()?.as_ref())))
+            .collect()
+    }
+
+    /// Answers of the parts that succeeded
+    pub fn parts(&self) -> Vec<(Phase, &Answer)> {
+        self.outcomes()
+            .into_iter()
+            .filter_map(|(phase, outcome)| Some((phase, outcome.ok()?)))
+            .collect()
+    }
+
+    /// Errors of the parts that failed
+    pub fn errors(&self) -> Vec<(Phase, &anyhow::Error)> {
+        self.outcomes()
+            .into_iter()
+            .filter_map(|(phase, outcome)| Some((phase, outcome.err()?)))
+            .collect()
+    }
+
+    /// Answers keyed by the output file stem they are written to, e.g. `d4p2`
+    pub fn answers(&self, name: &str) -> Vec<(String, &Answer)> {
+        self.parts()
+            .into_iter()
+            .map(|(phase, answer)| (format!("{}p{}", name, phase), answer))
+            .collect()
+    }
+}
+
+/// Which parts of a day to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Parts {
+    #[value(name = "1")]
+    One,
+    #[value(name = "2")]
+    Two,
+    #[default]
+    Both,
+}
+
+impl Parts {
+    pub fn includes(self, phase: Phase) -> bool {
+        match (self, phase) {
+            (_, Phase::Parse) | (Parts::Both, _) => true,
+            (Parts::One, phase) => phase == Phase::Part1,
+            (Parts::Two, phase) => phase == Phase::Part2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "1"),
+            Phase::Part2 => write!(f, "2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Timing {
+    pub phase: Phase,
+    pub elapsed: Duration,
+    /// Only measured with the `alloc-stats` feature
+    pub memory: Option<Usage>,
+}
+
+fn timed<T>(phase: Phase, timings: &mut Vec<Timing>, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let (result, memory) = memory::measure(f);
+    timings.push(Timing {
+        phase,
+        elapsed: start.elapsed(),
+        memory,
+    });
+    result
+}
+
+/// A puzzle example with its published answers, `None` for parts the example is not meant for
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+impl Example {
+    /// The parts this example has an answer for
+    pub fn parts(&self) -> Parts {
+        match (self.part1, self.part2) {
+            (Some(_), None) => Parts::One,
+            (None, Some(_)) => Parts::Two,
+            _ => Parts::Both,
+        }
+    }
+
+    pub fn expected(&self, phase: Phase) -> Option<&'static str> {
+        match phase {
+            Phase::Parse => None,
+            Phase::Part1 => self.part1,
+            Phase::Part2 => self.part2,
+        }
+    }
+}
+
+/// A knob a day reads through [`Context::param`], set with `--param` or in `aoc.toml`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Param {
+    pub name: &'static str,
+    /// Used unless the parameter is given, parsed like a given value
+    pub default: &'static str,
+    pub doc: &'static str,
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}  {}", self.name, self.default, self.doc)
+    }
+}
+
+/// An AOC day: parses its input once and answers both parts from the parsed form
+pub trait Solver: Sync {
+    type Parsed<'a>;
+
+    /// Name used on the command line and for input/output files, e.g. `d4`
+    fn name(&self) -> &'static str;
+    /// The event the puzzle is from, e.g. `2023`
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>>;
+    fn part1(&self, parsed: &Self::Parsed<'_>, ctx: &Context) -> anyhow::Result<Answer>;
+    fn part2(&self, parsed: &Self::Parsed<'_>, ctx: &Context) -> anyhow::Result<Answer>;
+
+    /// The puzzle's examples, checked by the `examples` subcommand
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    /// The parameters the day reads, values for any others are rejected
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    /// Whether blank lines at the start of the input are dropped before parsing
+    fn trim_leading(&self) -> bool {
+        true
+    }
+
+    fn solve(&self, input: &str) -> anyhow::Result<Solution> {
+        let input = normalize(input, self.trim_leading()).text;
+        let parsed = self.parse(&input)?;
+        let ctx = Context::default().for_day(self.name(), self.params());
+        Ok(Solution {
+            part1: self.part1(&parsed, &ctx)?,
+            part2: self.part2(&parsed, &ctx)?,
+        })
+    }
+}
+
+/// Object safe view of a day, this is what the registry holds
+pub trait Runner: Sync {
+    fn name(&self) -> &'static str;
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn examples(&self) -> &'static [Example];
+    fn params(&self) -> &'static [Param];
+    /// Runs the selected parts, pushing the wall-clock time of every phase that ran onto `timings`.
+    /// Only a failed parse is an error, a failed part is kept in the result next to the other one.
+    fn run(
+        &self,
+        input: &str,
+        parts: Parts,
+        ctx: &Context,
+        timings: &mut Vec<Timing>,
+    ) -> anyhow::Result<DayResult>;
+}
+
+impl<S: Solver> Runner for S {
+    fn name(&self) -> &'static str {
+        Solver::name(self)
+    }
+
+    fn year(&self) -> u16 {
+        Solver::year(self)
+    }
+
+    fn day(&self) -> u8 {
+        Solver::day(self)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        Solver::examples(self)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Solver::params(self)
+    }
+
+    fn run(
+        &self,
+        input: &str,
+        parts: Parts,
+        ctx: &Context,
+        timings: &mut Vec<Timing>,
+    ) -> anyhow::Result<DayResult> {
+        let input = normalize(input, self.trim_leading());
+        let ctx = &ctx.for_day(self.name(), self.params());
+        let parsed =
+            timed(Phase::Parse, timings, || self.parse(&input.text)).map_err(|e| match e
+                .downcast::<ParseError>()
+            {
+                Ok(parse_error) => parse_error.below(input.leading_lines).into(),
+                Err(e) => e,
+            })?;
+        let mut result = DayResult::default();
+        if parts.includes(Phase::Part1) {
+            result.part1 = Some(timed(Phase::Part1, timings, || self.part1(&parsed, ctx)));
+        }
+        if parts.includes(Phase::Part2) {
+            result.part2 = Some(timed(Phase::Part2, timings, || self.part2(&parsed, ctx)));
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_display_round_trip() {
+        let answers = vec![
+            Answer::from(42u32),
+            Answer::from(-7i32),
+            Answer::Big("123456789012345678901234567890".parse().unwrap()),
+            Answer::from("EFGH\nIJKL"),
+            Answer::Unsolved,
+        ];
+        for answer in answers {
+            assert_eq!(answer.to_string().parse::<Answer>().unwrap(), answer);
+        }
+    }
+
+    #[test]
+    fn test_answer_compares_integers_by_value() {
+        assert_eq!(Answer::Signed(5), Answer::Unsigned(5));
+        assert_eq!(Answer::Big(BigInt::from(5)), Answer::Unsigned(5));
+        assert_ne!(Answer::Signed(-5), Answer::Unsigned(5));
+        assert_eq!(Answer::from("5"), Answer::Unsigned(5));
+        assert_ne!(Answer::from("05"), Answer::Unsigned(5));
+        assert_ne!(Answer::Unsolved, Answer::Unsigned(0));
+        assert!(Answer::Signed(-5) < Answer::Unsigned(5));
+        assert!(Answer::Big(BigInt::from(1) << 70) > Answer::Unsigned(u64::MAX));
+        assert_eq!(Answer::from("a").partial_cmp(&Answer::Unsigned(5)), None);
+    }
+
+    #[test]
+    fn test_day_result_answers() {
+        let result = DayResult {
+            part1: None,
+            part2: Some(Ok(Answer::from(7u8))),
+        };
+        let answers = result.answers("d1");
+        assert_eq!(answers.len(), 1);
+        assert_eq!(answers[0].0, "d1p2");
+
+        let result = DayResult {
+            part1: Some(Ok(Answer::from(7u8))),
+            part2: Some(Err(anyhow::anyhow!("no start"))),
+        };
+        assert_eq!(result.answers("d1")[0].0, "d1p1");
+        assert_eq!(result.errors()[0].0, Phase::Part2);
+        assert_eq!(result.outcomes().len(), 2);
+        assert!(Parts::Two.includes(Phase::Parse));
+        assert!(!Parts::Two.includes(Phase::Part1));
+    }
+
+    #[test]
+    fn test_answer_matches() {
+        assert!(Answer::from(374usize).matches("374"));
+        assert!(Answer::from(374usize).matches(" 374\n"));
+        assert!(!Answer::from(374usize).matches("375"));
+        assert_eq!(Answer::from(-2i32), "-2");
+        assert!(Answer::from("12345").matches("12345"));
+        assert!(Answer::from("00123").matches(" 00123"));
+        assert!(!Answer::from("00123").matches("123"));
+        assert!(Answer::from("abc").matches("abc"));
+        assert!(!Answer::from("unsolved").matches("12"));
+    }
 }
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::anyhow;
+
+use crate::context::Context;
+use crate::runner;
+use crate::solution::{Answer, Parts, Runner};
+
+/// Known answers keyed by (year, output name)
+pub type Expected = HashMap<(u16, String), String>;
+
+/// Parses an expected-answers file, one `<year>.<output name> = <answer>` per line, e.g.
+/// `2023.d4p1 = 13`. Blank lines and lines starting with `#` are ignored.
+fn parse_expected(content: &str) -> anyhow::Result<Expected> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            let (key, answer) = line.split_once('=').ok_or(anyhow!(
+                "line {}: expected `year.name = answer`",
+                line_number
+            ))?;
+            let (year, key) = key.trim().split_once('.').ok_or(anyhow!(
+                "line {}: expected `year.name = answer`",
+                line_number
+            ))?;
+            let year = year
+                .parse()
+                .map_err(|e| anyhow!("line {}: {:?} is not a year: {}", line_number, year, e))?;
+            Ok(((year, key.to_string()), answer.trim().to_string()))
+        })
+        .collect()
+}
+
+pub fn load_expected(path: &Path) -> anyhow::Result<Expected> {
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow!("Unable to read {:?}: {}", path, e))?;
+    parse_expected(&content).map_err(|e| anyhow!("{:?}: {}", path, e))
+}
+
+/// Runs the given days and compares every answer against `expected`.
+/// Returns false if any answer differs or any day failed to run; missing answers only warn.
+pub fn verify(
+    solvers: &[&dyn Runner],
+    inputs_dir: &Path,
+    expected: &Expected,
+    ctx: &Context,
+) -> bool {
+    let mut ok = true;
+    for solver in solvers {
+        let result = match runner::solve(
+            *solver,
+            &runner::input_path(inputs_dir, *solver),
+            Parts::Both,
+            ctx,
+            &mut Vec::new(),
+        ) {
+            Ok(result) => result,
+            Err(e) => {
+                println!("{:<10} ERROR   {:#}", solver.name(), e);
+                ok = false;
+                continue;
+            }
+        };
+        for (phase, e) in result.errors() {
+            println!(
+                "{:<10} ERROR   {:#}",
+                format!("{}p{}", solver.name(), phase),
+                e
+            );
+            ok = false;
+        }
+        for (key, actual) in result.answers(solver.name()) {
+            match expected.get(&(solver.year(), key.clone())) {
+                Some(want) => ok &= compare(&key, want, actual),
+                None => println!("{:<10} MISSING {}", key, actual),
+            }
+        }
+    }
+    ok
+}
+
+/// Prints PASS or FAIL with both answers for `actual` against `want`, returns whether it matched
+pub(crate) fn compare(label: &str, want: &str, actual: &Answer) -> bool {
+    if actual.matches(want) {
+        println!("{:<10} PASS    {}", label, actual);
+        return true;
+    }
+    println!("{:<10} FAIL", label);
+    println!("    expected: {}", want);
+    println!("      actual: {}", actual);
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expected() {
+        let content = "# day 4
+2023.d4p1 = 13
+2023.d4p2=30
+
+2023.d1p1 =   142  
+2024.d1p1 = 11";
+        let expected = HashMap::from_iter(vec![
+            ((2023, "d4p1".to_string()), "13".to_string()),
+            ((2023, "d4p2".to_string()), "30".to_string()),
+            ((2023, "d1p1".to_string()), "142".to_string()),
+            ((2024, "d1p1".to_string()), "11".to_string()),
+        ]);
+        assert_eq!(parse_expected(content).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_expected_rejects_garbage() {
+        assert!(parse_expected("2023.d4p1 13").is_err());
+        assert!(parse_expected("d4p1 = 13").is_err());
+        assert!(parse_expected("y2023.d4p1 = 13").is_err());
+    }
+}
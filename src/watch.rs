@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::anyhow;
+use log::info;
+
+use crate::context::Context;
+use crate::ledger;
+use crate::runner;
+use crate::solution::{Parts, Phase, Runner};
+
+/// How often the watched file's modification time is checked
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Polls a file's modification time
+pub struct Watcher {
+    path: PathBuf,
+    last_modified: Option<SystemTime>,
+}
+
+impl Watcher {
+    pub fn new(path: &Path) -> Self {
+        Watcher {
+            path: path.to_path_buf(),
+            last_modified: None,
+        }
+    }
+
+    /// True the first time the file is seen and whenever its mtime moved since the last call
+    pub fn changed(&mut self) -> bool {
+        let modified = fs::metadata(&self.path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        if modified.is_some() && modified != self.last_modified {
+            self.last_modified = modified;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Re-runs `solver` every time `infile` changes, marking answers that differ from the previous run
+/// and checking each run against `ledger`. Only returns on error, stop it with Ctrl-C.
+pub fn watch(
+    solver: &dyn Runner,
+    infile: &Path,
+    parts: Parts,
+    ledger: &Path,
+    ctx: &Context,
+) -> anyhow::Result<()> {
+    if infile == Path::new("-") {
+        return Err(anyhow!("--watch needs an input file, not stdin"));
+    }
+    info!("Watching {:?} for changes", infile);
+    let mut watcher = Watcher::new(infile);
+    let mut previous: HashMap<Phase, String> = HashMap::new();
+    let highlight = io::stdout().is_terminal();
+    let mut run = 0;
+    loop {
+        if watcher.changed() {
+            run += 1;
+            println!("== {} run {} ==", solver.name(), run);
+            let mut timings = Vec::new();
+            match runner::solve(solver, infile, parts, ctx, &mut timings) {
+                Ok(result) => {
+                    ledger::check(ledger, solver, &result);
+                    for (phase, outcome) in result.outcomes() {
+                        let answer = match outcome {
+                            Ok(answer) => answer.to_string(),
+                            Err(e) => {
+                                println!("Part {}: error: {:#}", phase, e);
+                                continue;
+                            }
+                        };
+                        let elapsed = timings
+                            .iter()
+                            .find(|timing| timing.phase == phase)
+                            .map_or("-".to_string(), |timing| format!("{:.2?}", timing.elapsed));
+                        let change = change_marker(previous.get(&phase), &answer);
+                        if highlight && !change.is_empty() {
+                            println!(
+                                "\x1b[1;33mPart {}: {} ({}){}\x1b[0m",
+                                phase, answer, elapsed, change
+                            );
+                        } else {
+                            println!("Part {}: {} ({}){}", phase, answer, elapsed, change);
+                        }
+                        previous.insert(phase, answer);
+                    }
+                }
+                Err(e) => println!("error: {:#}", e),
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn change_marker(previous: Option<&String>, answer: &str) -> String {
+    match previous {
+        Some(previous) if previous != answer => format!("  CHANGED, was {}", previous),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use super::*;
+
+    #[test]
+    fn test_watcher() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        let mut watcher = Watcher::new(&path);
+        assert!(!watcher.changed());
+
+        fs::write(&path, "1").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        let later = SystemTime::now() + Duration::from_secs(10);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(watcher.changed());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_change_marker() {
+        assert_eq!(change_marker(None, "1"), "");
+        assert_eq!(change_marker(Some(&"1".to_string()), "1"), "");
+        assert_eq!(
+            change_marker(Some(&"1".to_string()), "2"),
+            "  CHANGED, was 1"
+        );
+    }
+}
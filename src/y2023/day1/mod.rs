@@ -0,0 +1,109 @@
+use crate::context::Context;
+use crate::solution::{Answer, Example, Solution, Solver};
+
+pub mod part1;
+pub mod part2;
+
+pub struct Day1;
+
+const WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// The calibration document, each line with the digits found in it
+pub struct Document<'a> {
+    pub input: &'a str,
+    pub lines: Vec<Line<'a>>,
+}
+
+pub struct Line<'a> {
+    pub text: &'a str,
+    /// The digits on the line in order
+    pub digits: Vec<u32>,
+    /// The digits on the line in order, counting spelled out ones like `two` too
+    pub spelled: Vec<u32>,
+}
+
+impl<'a> Line<'a> {
+    fn parse(text: &'a str) -> Self {
+        let digits = text.chars().filter_map(|c| c.to_digit(10)).collect();
+        // Words may overlap, as in `twone`, so look for one at every position
+        let spelled = (0..text.len())
+            .filter_map(|i| {
+                let rest = &text.as_bytes()[i..];
+                (rest[0] as char).to_digit(10).or_else(|| {
+                    WORDS
+                        .iter()
+                        .position(|word| rest.starts_with(word.as_bytes()))
+                        .map(|digit| digit as u32)
+                })
+            })
+            .collect();
+        Line {
+            text,
+            digits,
+            spelled,
+        }
+    }
+}
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet",
+        part1: Some("142"),
+        part2: None,
+    },
+    Example {
+        input: "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen",
+        part1: None,
+        part2: Some("281"),
+    },
+];
+
+impl Solver for Day1 {
+    type Parsed<'a> = Document<'a>;
+
+    fn name(&self) -> &'static str {
+        "d1"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(Document {
+            input,
+            lines: input.lines().map(Line::parse).collect(),
+        })
+    }
+
+    fn part1(&self, input: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        part1::run(input)
+    }
+
+    fn part2(&self, input: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        part2::run(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+pub fn run(input: &str) -> anyhow::Result<Solution> {
+    Day1.solve(input)
+}
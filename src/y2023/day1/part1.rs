@@ -0,0 +1,59 @@
+use crate::error::ParseError;
+use crate::solution::Answer;
+
+use super::Document;
+
+/// 1abc2
+/// ^   ^
+/// pqr3stu8vwx
+///    ^   ^
+/// a1b2c3d4e5f
+///  ^       ^
+/// treb7uchet
+///     ^
+pub fn run(document: &Document) -> anyhow::Result<Answer> {
+    let sum: u32 = document.lines.iter().map(|line| {
+        let missing = || ParseError::new(document.input, line.text, "a line with at least one digit");
+        Ok(10 * line.digits.first().ok_or_else(missing)? + line.digits.last().ok_or_else(missing)?)
+    }).sum::<Result<u32, ParseError>>()?;
+    Ok(sum.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solver;
+    use crate::y2023::day1::Day1;
+
+    fn run(input: &str) -> anyhow::Result<Answer> {
+        super::run(&Day1.parse(input)?)
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(run("1122zz").unwrap(), "12");
+        assert_eq!(run("zzz1111").unwrap(), "11");
+        assert_eq!(run("12ss34").unwrap(), "14");
+        assert_eq!(run("asd91sdfs212129asdas").unwrap(), "99");
+        assert_eq!(run("a5a").unwrap(), "55");
+    }
+
+    #[test]
+    fn test_run_multiline() {
+        assert_eq!(run("1\n2\n34\n55\n").unwrap(), "122");
+        assert_eq!(run(
+            "1abc2
+            pqr3stu8vwx
+            a1b2c3d4e5f
+            treb7uchet"
+        ).unwrap(), "142");
+    }
+
+    #[test]
+    fn test_run_line_without_digits() {
+        let error = run("1abc2\n\ntreb7uchet").unwrap_err().downcast::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+        let error = run("1abc2\nabc\n").unwrap_err().downcast::<ParseError>().unwrap();
+        assert_eq!((error.line, error.found.as_str()), (2, "abc"));
+    }
+}
\ No newline at end of file
@@ -1,27 +1,26 @@
-/// replaces words with digits and uses part1::run to calculate the sum
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let numbers = vec!["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
-    let digits = vec!["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
-    let lookup: Vec<_> = numbers.iter().enumerate().chain(digits.iter().enumerate()).collect();
-    let sum: usize = input.lines().filter_map(|line| {
-        let first = lookup.iter()
-            .filter_map(|(i, &word)|
-                line.find(word).map(|pos| (pos, i))
-            ).min_by_key(|(pos, _)| *pos).map(|(_, &i)| i)?;
-        let last = lookup.iter()
-            .filter_map(|(i, &word)|
-                line.rfind(word).map(|pos| (pos, i))
-            ).max_by_key(|(pos, _)| *pos).map(|(_, &i)| i)?;
-        Some(first * 10 + last)
+use crate::solution::Answer;
+
+use super::Document;
+
+/// Like part 1, but digits spelled out as words count too. Lines without any are skipped.
+pub fn run(document: &Document) -> anyhow::Result<Answer> {
+    let sum: u32 = document.lines.iter().filter_map(|line| {
+        Some(line.spelled.first()? * 10 + line.spelled.last()?)
     }).sum();
 
-    Ok(sum.to_string())
+    Ok(sum.into())
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::Solver;
+    use crate::y2023::day1::Day1;
+
+    fn run(input: &str) -> anyhow::Result<Answer> {
+        super::run(&Day1.parse(input)?)
+    }
 
     #[test]
     fn test_run() {
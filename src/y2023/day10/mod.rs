@@ -1,17 +1,16 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
 
 use anyhow::anyhow;
-use nom::branch::alt;
-use nom::character::complete::{char, newline};
-use nom::combinator::{map, opt};
-use nom::multi::{many1, separated_list1};
-use nom::sequence::delimited;
-use nom::IResult;
+use log::{debug, trace};
 
-use crate::solution::Solution;
+use crate::context::Context;
+use crate::error::ParseError;
+use crate::grid::{Grid, Offset, Pos};
+use crate::solution::{Answer, Example, Solution, Solver};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum Pipe {
+pub enum Pipe {
     NorthSouth,
     EastWest,
     NorthEast,
@@ -23,7 +22,21 @@ enum Pipe {
 }
 
 impl Pipe {
-    fn tb(&self, travel_direction: &TravelDirection) -> Option<[Vec<Coord>; 2]> {
+    fn from_char(c: char) -> Option<Pipe> {
+        match c {
+            '.' => Some(Pipe::Ground),
+            '|' => Some(Pipe::NorthSouth),
+            '-' => Some(Pipe::EastWest),
+            'F' => Some(Pipe::SouthEast),
+            '7' => Some(Pipe::SouthWest),
+            'L' => Some(Pipe::NorthEast),
+            'J' => Some(Pipe::NorthWest),
+            'S' => Some(Pipe::Start),
+            _ => None,
+        }
+    }
+
+    fn tb(&self, travel_direction: &TravelDirection) -> Option<[Vec<Offset>; 2]> {
         match (self, travel_direction) {
             (Pipe::EastWest, TravelDirection::East) => Some([
                 vec![(-1, -1), (0, -1), (1, -1)],
@@ -37,7 +50,7 @@ impl Pipe {
         }
     }
 
-    fn lr(&self, travel_direction: &TravelDirection) -> Option<[Vec<Coord>; 2]> {
+    fn lr(&self, travel_direction: &TravelDirection) -> Option<[Vec<Offset>; 2]> {
         match (self, travel_direction) {
             (Pipe::NorthSouth, TravelDirection::North) => Some([
                 vec![(-1, -1), (-1, 0), (-1, 1)],
@@ -51,7 +64,7 @@ impl Pipe {
         }
     }
 
-    fn tl_br(&self, travel_direction: &TravelDirection) -> Option<[Vec<Coord>; 2]> {
+    fn tl_br(&self, travel_direction: &TravelDirection) -> Option<[Vec<Offset>; 2]> {
         match (self, travel_direction) {
             (Pipe::NorthWest, TravelDirection::East) => Some([
                 vec![(-1, -1)],
@@ -72,7 +85,7 @@ impl Pipe {
             _ => None,
         }
     }
-    fn tr_bl(&self, travel_direction: &TravelDirection) -> Option<[Vec<Coord>; 2]> {
+    fn tr_bl(&self, travel_direction: &TravelDirection) -> Option<[Vec<Offset>; 2]> {
         match (self, travel_direction) {
             (Pipe::NorthEast, TravelDirection::South) => Some([
                 vec![(1, -1)],
@@ -98,7 +111,7 @@ impl Pipe {
         &self,
         loop_direction: &LoopDirection,
         travel_direction: &TravelDirection,
-    ) -> Vec<Coord> {
+    ) -> Vec<Offset> {
         let index = match loop_direction {
             LoopDirection::Clockwise => 1,
             LoopDirection::CounterClockwise => 0,
@@ -119,6 +132,22 @@ impl Pipe {
     }
 }
 
+impl fmt::Display for Pipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Pipe::Ground => '.',
+            Pipe::NorthSouth => '|',
+            Pipe::EastWest => '-',
+            Pipe::SouthEast => 'F',
+            Pipe::SouthWest => '7',
+            Pipe::NorthEast => 'L',
+            Pipe::NorthWest => 'J',
+            Pipe::Start => 'S',
+        };
+        write!(f, "{}", c)
+    }
+}
+
 #[derive(Debug)]
 enum TravelDirection {
     North,
@@ -137,6 +166,13 @@ impl TravelDirection {
             _ => Err(anyhow!("Unable to infer direction")),
         }
     }
+
+    fn between(from: &Coord, to: &Coord) -> anyhow::Result<Self> {
+        TravelDirection::new(
+            to.0 as isize - from.0 as isize,
+            to.1 as isize - from.1 as isize,
+        )
+    }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -145,19 +181,21 @@ enum LoopDirection {
     CounterClockwise,
 }
 
-type Coord = (isize, isize);
-type Graph = HashMap<Coord, Pipe>;
-
-fn infer_start_pipe(graph: &Graph, coord: &Coord) -> anyhow::Result<Pipe> {
-    let (x, y) = coord;
-    let north_coords = (x + 0, y - 1);
-    let south_coords = (x + 0, y + 1);
-    let east_coords = (x + 1, y + 0);
-    let west_coords = (x - 1, y + 0);
-    let north = graph.get(&north_coords);
-    let south = graph.get(&south_coords);
-    let east = graph.get(&east_coords);
-    let west = graph.get(&west_coords);
+pub type Coord = Pos;
+pub type Graph = Grid<Pipe>;
+
+/// The pipe at `offset` from `coord`, `None` off the edge of the grid
+fn pipe_at<'a>(graph: &'a Graph, coord: &Coord, offset: Offset) -> Option<&'a Pipe> {
+    graph
+        .offset(*coord, offset)
+        .and_then(|coord| graph.get(coord))
+}
+
+pub fn infer_start_pipe(graph: &Graph, coord: &Coord) -> anyhow::Result<Pipe> {
+    let north = pipe_at(graph, coord, (0, -1));
+    let south = pipe_at(graph, coord, (0, 1));
+    let east = pipe_at(graph, coord, (1, 0));
+    let west = pipe_at(graph, coord, (-1, 0));
     let north_connected = match north {
         Some(Pipe::NorthSouth) => true,
         Some(Pipe::SouthEast) => true,
@@ -198,21 +236,21 @@ fn infer_start_pipe(graph: &Graph, coord: &Coord) -> anyhow::Result<Pipe> {
     }
 }
 
-fn next_coordinates(coord: &Coord, pipe: Option<&Pipe>) -> Option<(Coord, Coord)> {
-    let (x, y) = coord;
-    match pipe {
-        Some(Pipe::NorthSouth) => Some(((x + 0, y + 1), (x + 0, y - 1))),
-        Some(Pipe::EastWest) => Some(((x + 1, y + 0), (x - 1, y + 0))),
-        Some(Pipe::NorthEast) => Some(((x + 0, y + -1), (x + 1, y + 0))),
-        Some(Pipe::NorthWest) => Some(((x + 0, y + -1), (x - 1, y + 0))),
-        Some(Pipe::SouthEast) => Some(((x + 0, y + 1), (x + 1, y + 0))),
-        Some(Pipe::SouthWest) => Some(((x + 0, y + 1), (x - 1, y + 0))),
-        _ => None,
-    }
+fn next_coordinates(graph: &Graph, coord: &Coord) -> Option<(Coord, Coord)> {
+    let (a, b) = match graph.get(*coord) {
+        Some(Pipe::NorthSouth) => ((0, 1), (0, -1)),
+        Some(Pipe::EastWest) => ((1, 0), (-1, 0)),
+        Some(Pipe::NorthEast) => ((0, -1), (1, 0)),
+        Some(Pipe::NorthWest) => ((0, -1), (-1, 0)),
+        Some(Pipe::SouthEast) => ((0, 1), (1, 0)),
+        Some(Pipe::SouthWest) => ((0, 1), (-1, 0)),
+        _ => return None,
+    };
+    Some((graph.offset(*coord, a)?, graph.offset(*coord, b)?))
 }
 
 fn step(graph: &Graph, prev: &Coord, current: &Coord) -> Option<Coord> {
-    let next = next_coordinates(&current, graph.get(&current));
+    let next = next_coordinates(graph, current);
     match next {
         Some((a, b)) => {
             if a == *prev {
@@ -225,30 +263,130 @@ fn step(graph: &Graph, prev: &Coord, current: &Coord) -> Option<Coord> {
     }
 }
 
+pub struct Day10;
+
+/// The pipe graph with the start tile replaced by the pipe it stands on
+pub struct Maze {
+    pub graph: Graph,
+    pub start: Coord,
+    pub directions: (Coord, Coord),
+}
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: ".....
+.S-7.
+.|.|.
+.L-J.
+.....",
+        part1: Some("4"),
+        part2: None,
+    },
+    Example {
+        input: "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF",
+        part1: Some("4"),
+        part2: None,
+    },
+    Example {
+        input: "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...",
+        part1: Some("8"),
+        part2: None,
+    },
+    Example {
+        input: "7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ",
+        part1: Some("8"),
+        part2: None,
+    },
+    Example {
+        input: "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        part1: None,
+        part2: Some("4"),
+    },
+];
+
+impl Solver for Day10 {
+    type Parsed<'a> = Maze;
+
+    fn name(&self) -> &'static str {
+        "d10"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        let mut graph = parse_graph(input)?;
+        let start = find_start(&graph).ok_or(anyhow!("Unable to find start"))?;
+        let start_pipe = infer_start_pipe(&graph, &start)?;
+        graph[start] = start_pipe;
+        let directions =
+            next_coordinates(&graph, &start).ok_or(anyhow!("The start pipe leads off the grid"))?;
+        Ok(Maze {
+            graph,
+            start,
+            directions,
+        })
+    }
+
+    fn part1(&self, maze: &Self::Parsed<'_>, ctx: &Context) -> anyhow::Result<Answer> {
+        Ok(part1(&maze.graph, maze.start, &maze.directions, ctx)?.into())
+    }
+
+    fn part2(&self, maze: &Self::Parsed<'_>, ctx: &Context) -> anyhow::Result<Answer> {
+        Ok(part2(&maze.graph, maze.start, &maze.directions, ctx)?.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
 pub fn run(input: &str) -> anyhow::Result<Solution> {
-    let mut graph = parse_graph(input).expect("failed to parse").1;
-    let start = find_start(&graph)
-        .ok_or(anyhow!("Unable to find start"))?
-        .clone();
-    let start_pipe = infer_start_pipe(&graph, &start)?;
-    graph.insert(start, start_pipe);
-    let directions = next_coordinates(&start, graph.get(&start)).unwrap();
-    let part1_solution = part1(&graph, start, &directions)?;
-    let part2_solution = part2(&graph, start, &directions)?;
-    Ok(Solution {
-        part1: part1_solution.to_string(),
-        part2: part2_solution.to_string(),
-    })
+    Day10.solve(input)
 }
 
-fn walk<F>(graph: &Graph, start: &Coord, first: &Coord, mut f: F)
+/// Follows the pipes from `start` through `first` until back at `start` or at a dead end,
+/// calling `f` on every pipe. Checks `ctx` at every step in case the pipes never lead back.
+fn walk<F>(
+    graph: &Graph,
+    start: &Coord,
+    first: &Coord,
+    ctx: &Context,
+    mut f: F,
+) -> anyhow::Result<()>
 where
     F: FnMut(&Graph, &Coord, &Coord) -> anyhow::Result<()>,
 {
     let mut prev = start.clone();
     let mut current = first.clone();
     loop {
-        f(&graph, &prev, &current).unwrap();
+        ctx.check()?;
+        f(&graph, &prev, &current)?;
         let next = step(&graph, &prev, &current);
         match next {
             Some(next) => {
@@ -261,20 +399,31 @@ where
             None => break,
         }
     }
+    Ok(())
 }
 
-fn part1(graph: &Graph, start: Coord, directions: &(Coord, Coord)) -> anyhow::Result<usize> {
+fn part1(
+    graph: &Graph,
+    start: Coord,
+    directions: &(Coord, Coord),
+    ctx: &Context,
+) -> anyhow::Result<usize> {
     let mut steps = 0;
-    let _ = walk(graph, &start, &directions.0, |_, _, _| Ok(steps += 1));
+    walk(graph, &start, &directions.0, ctx, |_, _, _| Ok(steps += 1))?;
     Ok(steps / 2)
 }
 
-fn part2(graph: &Graph, start: Coord, directions: &(Coord, Coord)) -> anyhow::Result<usize> {
+fn part2(
+    graph: &Graph,
+    start: Coord,
+    directions: &(Coord, Coord),
+    ctx: &Context,
+) -> anyhow::Result<usize> {
     let mut pipe_loop: HashSet<Coord> = HashSet::new();
-    let _ = walk(graph, &start, &directions.0, |_, _, current| {
+    walk(graph, &start, &directions.0, ctx, |_, _, current| {
         pipe_loop.insert(current.clone());
         Ok(())
-    });
+    })?;
     let pipe_loop = pipe_loop;
     let outside: Coord = (0, 0);
     assert_eq!(pipe_loop.contains(&outside), false);
@@ -282,12 +431,12 @@ fn part2(graph: &Graph, start: Coord, directions: &(Coord, Coord)) -> anyhow::Re
 
     let mut inside_candidates: HashSet<Coord> = HashSet::new();
     let mut loop_direction: Option<LoopDirection> = None;
-    let _ = walk(graph, &start, &directions.0, |_, prev, current| {
+    walk(graph, &start, &directions.0, ctx, |_, prev, current| {
         if let Some(_) = loop_direction {
             return Ok(());
         }
-        let pipe = graph.get(&current).ok_or(anyhow!("Unable to find pipe"))?;
-        let direction_of_travel = TravelDirection::new(current.0 - prev.0, current.1 - prev.1)?;
+        let pipe = graph.get(*current).ok_or(anyhow!("Unable to find pipe"))?;
+        let direction_of_travel = TravelDirection::between(prev, current)?;
         loop_direction = match (&direction_of_travel, &pipe) {
             (TravelDirection::North, Pipe::SouthEast) => Some(LoopDirection::Clockwise),
             (TravelDirection::North, Pipe::SouthWest) => Some(LoopDirection::CounterClockwise),
@@ -300,22 +449,28 @@ fn part2(graph: &Graph, start: Coord, directions: &(Coord, Coord)) -> anyhow::Re
             _ => loop_direction,
         };
         Ok(())
-    });
+    })?;
     let loop_direction = loop_direction.ok_or(anyhow!("Unable to infer loop direction"))?;
-    dbg!(loop_direction);
-    let _ = walk(graph, &start, &directions.0, |_, prev, current| {
-        let pipe = graph.get(&current).ok_or(anyhow!("Unable to find pipe"))?;
-        let direction_of_travel = TravelDirection::new(current.0 - prev.0, current.1 - prev.1)?;
+    debug!("loop direction: {:?}", loop_direction);
+    walk(graph, &start, &directions.0, ctx, |_, prev, current| {
+        let pipe = graph.get(*current).ok_or(anyhow!("Unable to find pipe"))?;
+        let direction_of_travel = TravelDirection::between(prev, current)?;
         let candidates: Vec<_> = pipe
             .nodes_to_check(&loop_direction, &direction_of_travel)
             .iter()
-            .map(|x| (x.0 + current.0, x.1 + current.1))
+            .filter_map(|offset| graph.offset(*current, *offset))
             .collect();
 
-        dbg!(&current, &pipe, &direction_of_travel);
-        inside_candidates.extend(dbg!(candidates));
+        trace!(
+            "{:?} {:?} heading {:?}, inside candidates {:?}",
+            current,
+            pipe,
+            direction_of_travel,
+            candidates
+        );
+        inside_candidates.extend(candidates);
         Ok(())
-    });
+    })?;
 
     assert!(inside_candidates
         .iter()
@@ -324,14 +479,14 @@ fn part2(graph: &Graph, start: Coord, directions: &(Coord, Coord)) -> anyhow::Re
         .iter()
         .filter(|coord| !pipe_loop.contains(coord))
         .collect();
-    dbg!(&inside_starting_points);
+    debug!("inside starting points: {:?}", inside_starting_points);
 
     let inside_nodes = flood_fill(graph, &pipe_loop, &inside_starting_points)?;
 
     Ok(inside_nodes.iter().count())
 }
 
-fn flood_fill(
+pub fn flood_fill(
     graph: &Graph,
     pipe_loop: &HashSet<Coord>,
     start: &Vec<&Coord>,
@@ -344,9 +499,6 @@ fn flood_fill(
             break;
         }
         let current = current.unwrap();
-        if graph.contains_key(&current) == false {
-            continue;
-        }
         if pipe_loop.contains(&current) {
             continue;
         }
@@ -354,55 +506,21 @@ fn flood_fill(
             continue;
         }
         out_of_loop.insert(current.clone());
-        queue.extend(vec![
-            (current.0, current.1 + 1),
-            (current.0, current.1 - 1),
-            (current.0 + 1, current.1),
-            (current.0 - 1, current.1),
-        ]);
+        queue.extend(graph.neighbours4(current));
     }
 
     Ok(out_of_loop)
 }
 
-fn find_start(graph: &Graph) -> Option<&Coord> {
+pub fn find_start(graph: &Graph) -> Option<Coord> {
     graph
         .iter()
         .find(|(_, pipe)| **pipe == Pipe::Start)
         .map(|(coord, _)| coord)
 }
 
-fn parse_graph(input: &str) -> IResult<&str, Graph> {
-    map(
-        delimited(
-            opt(newline),
-            separated_list1(
-                newline,
-                many1(alt((
-                    map(char('.'), |_| Pipe::Ground),
-                    map(char('|'), |_| Pipe::NorthSouth),
-                    map(char('-'), |_| Pipe::EastWest),
-                    map(char('F'), |_| Pipe::SouthEast),
-                    map(char('7'), |_| Pipe::SouthWest),
-                    map(char('L'), |_| Pipe::NorthEast),
-                    map(char('J'), |_| Pipe::NorthWest),
-                    map(char('S'), |_| Pipe::Start),
-                ))),
-            ),
-            opt(newline),
-        ),
-        |lines| {
-            lines
-                .iter()
-                .enumerate()
-                .flat_map(|(y, line)| {
-                    line.iter()
-                        .enumerate()
-                        .map(move |(x, pipe)| ((x as isize, y as isize).clone(), (*pipe).clone()))
-                })
-                .collect()
-        },
-    )(input)
+pub fn parse_graph(input: &str) -> Result<Graph, ParseError> {
+    Grid::parse(input, "a grid of `|-LJ7F.S` tiles", Pipe::from_char)
 }
 
 #[cfg(test)]
@@ -412,7 +530,7 @@ mod tests {
     #[test]
     fn test_flood_fill() -> anyhow::Result<()> {
         let input = ".....";
-        let graph = parse_graph(input)?.1;
+        let graph = parse_graph(input)?;
         let flood = flood_fill(&graph, &HashSet::new(), &vec![&(0, 0)])?;
         assert_eq!(
             flood,
@@ -420,7 +538,7 @@ mod tests {
         );
 
         let input = "..S7.\n..LJ.\n.....";
-        let graph = parse_graph(input)?.1;
+        let graph = parse_graph(input)?;
         let pipe_loop = HashSet::from_iter(vec![(2, 0), (3, 0), (2, 1), (3, 1)]);
         let flood = flood_fill(&graph, &pipe_loop, &vec![&(0, 0)])?;
         assert_eq!(
@@ -446,9 +564,9 @@ mod tests {
     #[test]
     fn test_infer_start() -> anyhow::Result<()> {
         let input = "S-7\n|.|\nL-J";
-        let graph = parse_graph(input)?.1;
+        let graph = parse_graph(input)?;
         let start = find_start(&graph).ok_or(anyhow!("Unable to find start"))?;
-        let actual = infer_start_pipe(&graph, start)?;
+        let actual = infer_start_pipe(&graph, &start)?;
         let expected = Pipe::SouthEast;
         assert_eq!(actual, expected);
         Ok(())
@@ -456,31 +574,31 @@ mod tests {
     #[test]
     fn test_parse_graph() -> anyhow::Result<()> {
         let input = ".....";
-        let actual = parse_graph(input)?.1;
-        let expected = Graph::from_iter(vec![
-            ((0, 0), Pipe::Ground),
-            ((1, 0), Pipe::Ground),
-            ((2, 0), Pipe::Ground),
-            ((3, 0), Pipe::Ground),
-            ((4, 0), Pipe::Ground),
-        ]);
+        let actual = parse_graph(input)?;
+        let expected = Graph::from_fn(5, 1, |_| Pipe::Ground);
         assert_eq!(actual, expected);
 
-        let input = "F-7\n|.|\nL-J\nS";
-        let actual = parse_graph(input)?.1;
-        let expected = Graph::from_iter(vec![
-            ((0, 0), Pipe::SouthEast),
-            ((1, 0), Pipe::EastWest),
-            ((2, 0), Pipe::SouthWest),
-            ((0, 1), Pipe::NorthSouth),
-            ((1, 1), Pipe::Ground),
-            ((2, 1), Pipe::NorthSouth),
-            ((0, 2), Pipe::NorthEast),
-            ((1, 2), Pipe::EastWest),
-            ((2, 2), Pipe::NorthWest),
-            ((0, 3), Pipe::Start),
-        ]);
-        assert_eq!(actual, expected);
+        let input = "F-7\n|.|\nL-J\nS..";
+        let actual = parse_graph(input)?;
+        let expected = vec![
+            ((0, 0), &Pipe::SouthEast),
+            ((1, 0), &Pipe::EastWest),
+            ((2, 0), &Pipe::SouthWest),
+            ((0, 1), &Pipe::NorthSouth),
+            ((1, 1), &Pipe::Ground),
+            ((2, 1), &Pipe::NorthSouth),
+            ((0, 2), &Pipe::NorthEast),
+            ((1, 2), &Pipe::EastWest),
+            ((2, 2), &Pipe::NorthWest),
+            ((0, 3), &Pipe::Start),
+            ((1, 3), &Pipe::Ground),
+            ((2, 3), &Pipe::Ground),
+        ];
+        assert_eq!(actual.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(actual.to_string(), input);
+
+        let error = parse_graph("F-7\nL-X").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
         Ok(())
     }
 
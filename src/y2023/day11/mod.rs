@@ -0,0 +1,262 @@
+use anyhow::anyhow;
+use itertools::Itertools;
+
+use crate::context::Context;
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Example, Param, Solution, Solver};
+
+pub struct Day11;
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....",
+    part1: Some("374"),
+    part2: Some("82000210"),
+}];
+
+const PARAMS: &[Param] = &[Param {
+    name: "expansion",
+    default: "1000000",
+    doc: "How many rows or columns each empty one becomes in part 2",
+}];
+
+impl Solver for Day11 {
+    type Parsed<'a> = Grid<bool>;
+
+    fn name(&self) -> &'static str {
+        "d11"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(parse_image(input)?)
+    }
+
+    fn part1(&self, input: &Self::Parsed<'_>, ctx: &Context) -> anyhow::Result<Answer> {
+        Ok(part1(input, ctx)?.into())
+    }
+
+    fn part2(&self, input: &Self::Parsed<'_>, ctx: &Context) -> anyhow::Result<Answer> {
+        let expansion: usize = ctx.param("expansion")?;
+        if expansion < 1 {
+            return Err(anyhow!("expansion must be at least 1, got {}", expansion));
+        }
+        Ok(part2(input, expansion, ctx)?.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
+
+pub fn run(input: &str) -> anyhow::Result<Solution> {
+    Day11.solve(input)
+}
+
+fn part1(image: &Grid<bool>, ctx: &Context) -> anyhow::Result<usize> {
+    let universe = expand_universe(image, 2);
+    Ok(min_distance_pairs(universe, ctx).iter().sum())
+}
+
+fn part2(image: &Grid<bool>, expansion_factor: usize, ctx: &Context) -> anyhow::Result<usize> {
+    let universe = expand_universe(image, expansion_factor);
+    Ok(min_distance_pairs(universe, ctx).iter().sum())
+}
+
+pub type Galaxy = Pos;
+pub type Universe = Vec<Galaxy>;
+
+/// The image as a grid of galaxies, `#` is a galaxy and `.` empty space
+pub fn parse_image(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse(input, "`#` or `.`", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+pub fn universe(image: &Grid<bool>) -> Universe {
+    image
+        .iter()
+        .filter(|(_, galaxy)| **galaxy)
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+/// How far each row or column moves once every empty one before it grew `offset` larger
+fn offsets<'a>(
+    lines: impl Iterator<Item = impl Iterator<Item = &'a bool>>,
+    offset: usize,
+) -> Vec<usize> {
+    lines
+        .scan(0usize, |acc, mut line| {
+            if !line.any(|galaxy| *galaxy) {
+                *acc += offset;
+            }
+            Some(*acc)
+        })
+        .collect()
+}
+
+pub fn expand_universe(image: &Grid<bool>, expansion_factor: usize) -> Universe {
+    let offset = expansion_factor - 1;
+    let small_universe = universe(image);
+    let x_offsets = offsets(image.columns(), offset);
+    let y_offsets = offsets(image.rows().map(|row| row.iter()), offset);
+
+    small_universe
+        .iter()
+        .map(|(x, y)| {
+            let x_offset = x_offsets[*x];
+            let y_offset = y_offsets[*y];
+            (*x + x_offset, *y + y_offset)
+        })
+        .collect::<Universe>()
+}
+
+fn l1(ab: (&Galaxy, &Galaxy)) -> usize {
+    let (a, b) = ab;
+    return ((a.0 as isize - b.0 as isize).abs() + (a.1 as isize - b.1 as isize).abs()) as usize;
+}
+
+pub fn min_distance_pairs(universe: Universe, ctx: &Context) -> Vec<usize> {
+    let pairs = universe.len() * universe.len().saturating_sub(1) / 2;
+    ctx.progress("pairs", pairs, universe.iter().combinations(2))
+        .map(|ab| (ab[0], ab[1]))
+        .map(l1)
+        .sorted()
+        .collect::<Vec<_>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Params;
+    use crate::solution::{Parts, Runner};
+
+    #[test]
+    fn test_min_distance() {
+        let input = "\
+.#..#
+.....
+#....";
+        let expected = vec![3, 3, 6];
+        let universe = universe(&parse_image(input).unwrap());
+        let distance_pairs = min_distance_pairs(universe, &Context::default());
+        assert_eq!(distance_pairs, expected);
+    }
+
+    #[test]
+    fn test_universe() {
+        let input = ".#.\n..#";
+        let expected = vec![(1, 0), (2, 1)];
+        assert_eq!(universe(&parse_image(input).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_expand_universe() -> anyhow::Result<()> {
+        let input = ".#.
+...
+..#";
+        let expected = "..#.
+....
+....
+...#";
+        assert_eq!(
+            expand_universe(&parse_image(input)?, 2),
+            universe(&parse_image(expected)?)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+        assert_eq!(run(input).unwrap().part1, "374");
+        assert_eq!(run(input).unwrap().part1, "374");
+    }
+    #[test]
+    fn test_part2() -> anyhow::Result<()> {
+        let input = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+        let image = parse_image(input)?;
+        let universe = expand_universe(&image, 10);
+        assert_eq!(
+            min_distance_pairs(universe, &Context::default())
+                .iter()
+                .sum::<usize>(),
+            1030usize
+        );
+        let universe = expand_universe(&image, 100);
+        assert_eq!(
+            min_distance_pairs(universe, &Context::default())
+                .iter()
+                .sum::<usize>(),
+            8410usize
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_expansion_param() -> anyhow::Result<()> {
+        let params = Params::from([(
+            "d11".to_string(),
+            [("expansion".to_string(), "10".to_string())].into(),
+        )]);
+        let ctx = Context::default().with_params(params);
+        let result = Day11.run(EXAMPLES[0].input, Parts::Two, &ctx, &mut Vec::new())?;
+        assert_eq!(result.part2.unwrap()?, Answer::from(1030usize));
+        Ok(())
+    }
+
+    #[test]
+    fn test_expansion_zero() {
+        let params = Params::from([(
+            "d11".to_string(),
+            [("expansion".to_string(), "0".to_string())].into(),
+        )]);
+        let ctx = Context::default().with_params(params);
+        let error = Day11
+            .run(EXAMPLES[0].input, Parts::Two, &ctx, &mut Vec::new())
+            .and_then(|result| result.part2.unwrap())
+            .unwrap_err();
+        assert_eq!(error.to_string(), "expansion must be at least 1, got 0");
+    }
+}
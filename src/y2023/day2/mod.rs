@@ -0,0 +1,92 @@
+use nom::sequence::pair;
+
+use crate::context::Context;
+use crate::error::parse_lines;
+use crate::solution::{Answer, Example, Param, Solution, Solver};
+
+pub mod part1;
+pub(crate) mod part2;
+
+pub struct Day2;
+
+/// A game's id and the cubes shown in each of its rounds
+pub type Game = (usize, Vec<part1::CubeCount>);
+
+const GAME: &str = "a game like `Game 1: 3 blue, 4 red; 1 red, 2 green`";
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+    part1: Some("8"),
+    part2: Some("2286"),
+}];
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "red",
+        default: "12",
+        doc: "Red cubes in the bag for part 1",
+    },
+    Param {
+        name: "green",
+        default: "13",
+        doc: "Green cubes in the bag for part 1",
+    },
+    Param {
+        name: "blue",
+        default: "14",
+        doc: "Blue cubes in the bag for part 1",
+    },
+];
+
+impl Solver for Day2 {
+    type Parsed<'a> = Vec<Game>;
+
+    fn name(&self) -> &'static str {
+        "d2"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(parse_lines(
+            input,
+            pair(part1::game_id, part1::parse_game),
+            GAME,
+        )?)
+    }
+
+    fn part1(&self, games: &Self::Parsed<'_>, ctx: &Context) -> anyhow::Result<Answer> {
+        let limit = part1::CubeCount {
+            r: ctx.param("red")?,
+            g: ctx.param("green")?,
+            b: ctx.param("blue")?,
+        };
+        part1::run(games, &limit)
+    }
+
+    fn part2(&self, games: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        part2::run(games)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
+
+pub fn run(input: &str) -> anyhow::Result<Solution> {
+    Day2.solve(input)
+}
@@ -1,3 +1,4 @@
+use std::cmp::max;
 use std::ops::{Add, AddAssign};
 
 use nom::bytes::complete::tag;
@@ -6,11 +7,15 @@ use nom::combinator::{map_res, opt};
 use nom::multi::fold_many0;
 use nom::IResult;
 
+use crate::solution::Answer;
+
+use super::Game;
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
-struct CubeCount {
-    r: u32,
-    g: u32,
-    b: u32,
+pub struct CubeCount {
+    pub r: u32,
+    pub g: u32,
+    pub b: u32,
 }
 
 impl Add<CubeCount> for CubeCount {
@@ -34,54 +39,66 @@ impl AddAssign<CubeCount> for CubeCount {
 }
 
 impl CubeCount {
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         CubeCount { r: 0, g: 0, b: 0 }
     }
 
     fn all_lt(&self, other: &CubeCount) -> bool {
         self.r <= other.r && self.g <= other.g && self.b <= other.b
     }
+
+    /// Raises each colour to at least the count in `other`
+    pub(super) fn bump(&mut self, other: &CubeCount) {
+        self.r = max(self.r, other.r);
+        self.g = max(self.g, other.g);
+        self.b = max(self.b, other.b);
+    }
+
+    pub(super) fn power(&self) -> u32 {
+        self.r * self.g * self.b
+    }
 }
 
-fn game_id(input: &str) -> IResult<&str, usize> {
+pub(super) fn game_id(input: &str) -> IResult<&str, usize> {
     let (input, _) = tag("Game ")(input)?;
     let (input, game_id) = map_res(digit1, str::parse)(input)?;
     let (input, _) = tag(": ")(input)?;
     Ok((input, game_id))
 }
 
-fn str_to_count(input: &str, count: u32) -> CubeCount {
+fn str_to_count(input: &str, count: u32) -> Option<CubeCount> {
     match input {
-        "red" => CubeCount {
+        "red" => Some(CubeCount {
             r: count,
             g: 0,
             b: 0,
-        },
-        "green" => CubeCount {
+        }),
+        "green" => Some(CubeCount {
             r: 0,
             g: count,
             b: 0,
-        },
-        "blue" => CubeCount {
+        }),
+        "blue" => Some(CubeCount {
             r: 0,
             g: 0,
             b: count,
-        },
-        _ => panic!("Unknown color: {:?}", input),
+        }),
+        _ => None,
     }
 }
 
-/// ```rust
-/// assert_eq!(parse_color("3 blue"), Ok(("", CubeCount { r: 0, g: 0, b: 3 })));
-/// ```
-///
+/// Parses one colour like `3 blue`, along with the comma after it
 fn parse_color(input: &str) -> IResult<&str, CubeCount> {
     let (input, count): (&str, u32) = map_res(digit1, str::parse)(input)?;
     let (input, _) = tag(" ")(input)?;
-    let (input, color) = alpha1(input)?;
-    let (input, _) = opt(tag(","))(input)?; // consume the comma
+    let (rest, color) = alpha1(input)?;
+    let cubes = str_to_count(color, count).ok_or(nom::Err::Failure(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::Tag,
+    )))?;
+    let (input, _) = opt(tag(","))(rest)?; // consume the comma
     let (input, _) = opt(tag(" "))(input)?; // consume the comma
-    Ok((input, str_to_count(color, count)))
+    Ok((input, cubes))
 }
 
 fn parse_round(input: &str) -> IResult<&str, CubeCount> {
@@ -101,25 +118,18 @@ fn parse_round(input: &str) -> IResult<&str, CubeCount> {
 }
 
 /// pareses: '3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green, 1 green'
-fn parse_game(input: &str) -> IResult<&str, Vec<CubeCount>> {
+pub(super) fn parse_game(input: &str) -> IResult<&str, Vec<CubeCount>> {
     fold_many0(parse_round, Vec::new, |mut acc, item| {
         acc.push(item);
         acc
     })(input)
 }
 
-pub fn run(input: &str) -> anyhow::Result<String> {
-    let limit = CubeCount {
-        r: 12,
-        g: 13,
-        b: 14,
-    };
-    let sum: usize = input
-        .lines()
-        .filter_map(|line| game_id(line).ok())
-        .filter_map(|(line, game_id)| {
-            let (_, cubes) = parse_game(line).ok()?;
-            if let true = cubes.iter().all(|count| count.all_lt(&limit)) {
+pub fn run(games: &[Game], limit: &CubeCount) -> anyhow::Result<Answer> {
+    let sum: usize = games
+        .iter()
+        .filter_map(|(game_id, cubes)| {
+            if let true = cubes.iter().all(|count| count.all_lt(limit)) {
                 Some(game_id)
             } else {
                 None
@@ -127,12 +137,24 @@ pub fn run(input: &str) -> anyhow::Result<String> {
         })
         // sum the remaining Game IDs
         .sum();
-    Ok(sum.to_string())
+    Ok(sum.into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::Solver;
+    use crate::y2023::day2::Day2;
+
+    fn run(input: &str, limit: &CubeCount) -> anyhow::Result<Answer> {
+        super::run(&Day2.parse(input)?, limit)
+    }
+
+    const LIMIT: CubeCount = CubeCount {
+        r: 12,
+        g: 13,
+        b: 14,
+    };
 
     #[test]
     fn test_parse_color() {
@@ -203,13 +225,16 @@ mod tests {
     #[test]
     fn test_part1() {
         assert_eq!(
-            run("
+            run(
+                "
 Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
-            ")
+            ",
+                &LIMIT
+            )
             .unwrap(),
             "8"
         );
@@ -217,7 +242,7 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
         assert_eq!(run(
             "
 Game 98: 13 green, 1 red, 5 blue; 2 red, 5 green, 7 blue; 19 green, 5 blue; 4 blue, 13 green; 5 green, 8 blue
-"
+", &LIMIT
         ).unwrap(), "0");
 
         assert_eq!(run(
@@ -225,7 +250,7 @@ Game 98: 13 green, 1 red, 5 blue; 2 red, 5 green, 7 blue; 19 green, 5 blue; 4 bl
 Game 1: 1 blue; 4 green, 5 blue; 11 red, 3 blue, 11 green; 1 red, 10 green, 4 blue; 17 red, 12 green, 7 blue; 3 blue, 19 green, 15 red
 Game 99: 11 red, 8 green; 16 red, 10 green; 9 red, 6 green; 3 blue, 2 red, 4 green
 Game 100: 4 red, 2 blue, 4 green; 2 green, 1 red, 1 blue; 3 green, 4 blue, 5 red; 18 red, 2 blue; 9 red, 5 green, 4 blue
-    "
+    ", &LIMIT
         ).unwrap(), "0");
     }
 }
@@ -0,0 +1,59 @@
+use crate::solution::Answer;
+
+use super::part1::CubeCount;
+use super::Game;
+
+pub fn run(games: &[Game]) -> anyhow::Result<Answer> {
+    let sum: usize = games
+        .iter()
+        .map(|(_game_id, rounds)| {
+            let mut fewest = CubeCount::new();
+            for round in rounds {
+                fewest.bump(round);
+            }
+            fewest.power() as usize
+        })
+        // sum the cubes set power
+        .sum();
+    Ok(sum.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solver;
+    use crate::y2023::day2::Day2;
+
+    fn run(input: &str) -> anyhow::Result<Answer> {
+        super::run(&Day2.parse(input)?)
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            run("
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+            ")
+            .unwrap(),
+            "8"
+        );
+
+        assert_eq!(run(
+            "
+Game 98: 13 green, 1 red, 5 blue; 2 red, 5 green, 7 blue; 19 green, 5 blue; 4 blue, 13 green; 5 green, 8 blue
+"
+        ).unwrap(), "0");
+
+        assert_eq!(run(
+            "
+Game 1: 1 blue; 4 green, 5 blue; 11 red, 3 blue, 11 green; 1 red, 10 green, 4 blue; 17 red, 12 green, 7 blue; 3 blue, 19 green, 15 red
+Game 99: 11 red, 8 green; 16 red, 10 green; 9 red, 6 green; 3 blue, 2 red, 4 green
+Game 100: 4 red, 2 blue, 4 green; 2 green, 1 red, 1 blue; 3 green, 4 blue, 5 red; 18 red, 2 blue; 9 red, 5 green, 4 blue
+    "
+        ).unwrap(), "0");
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use crate::context::Context;
+use crate::grid::{Grid, Pos};
+use crate::solution::{Answer, Example, Solution, Solver};
+
+pub mod part1;
+pub mod part2;
+
+pub struct Day3;
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..",
+    part1: Some("4361"),
+    part2: Some("467835"),
+}];
+
+impl Solver for Day3 {
+    type Parsed<'a> = Grid<char>;
+
+    fn name(&self) -> &'static str {
+        "d3"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(Grid::parse(
+            input,
+            "a schematic of digits, `.` and symbols",
+            Some,
+        )?)
+    }
+
+    fn part1(&self, input: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        part1::run(input)
+    }
+
+    fn part2(&self, input: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        part2::run(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+pub fn run(input: &str) -> anyhow::Result<Solution> {
+    Day3.solve(input)
+}
+
+/// A part number, `(x, y)` is the position of its first digit
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Number {
+    pub x: usize,
+    pub y: usize,
+    pub value: u32,
+}
+
+impl Number {
+    /// The positions around the number's digits
+    pub fn neighbours(&self, schematic: &Grid<char>) -> HashSet<Pos> {
+        let digits = self.value.to_string().len();
+        (self.x..self.x + digits)
+            .flat_map(|x| schematic.neighbours8((x, self.y)))
+            .collect()
+    }
+}
+
+/// Every number in the schematic, row by row
+pub fn find_numbers(schematic: &Grid<char>) -> anyhow::Result<Vec<Number>> {
+    let mut numbers = Vec::new();
+    for (y, row) in schematic.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let digits = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if digits > 0 {
+                let value = row[x..x + digits].iter().collect::<String>().parse()?;
+                numbers.push(Number { x, y, value });
+            }
+            x += digits.max(1);
+        }
+    }
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMATIC: &str = "\
+12.$
+..3.
+45*.";
+
+    #[test]
+    fn test_find_numbers() {
+        let schematic = Day3.parse(SCHEMATIC).unwrap();
+        let numbers = find_numbers(&schematic).unwrap();
+        assert_eq!(
+            numbers,
+            vec![
+                Number {
+                    x: 0,
+                    y: 0,
+                    value: 12
+                },
+                Number {
+                    x: 2,
+                    y: 1,
+                    value: 3
+                },
+                Number {
+                    x: 0,
+                    y: 2,
+                    value: 45
+                }
+            ]
+        );
+        assert_eq!(numbers[0].neighbours(&schematic).len(), 6);
+        assert_eq!(numbers[1].neighbours(&schematic).len(), 8);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::grid::Grid;
+use crate::solution::Answer;
+
+use super::{find_numbers, Number};
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+pub fn run(schematic: &Grid<char>) -> anyhow::Result<Answer> {
+    let sum: usize = find_numbers(schematic)?
+        .iter()
+        .filter_map(|n| {
+            if has_neighbors(schematic, n) {
+                Some(n.value as usize)
+            } else {
+                None
+            }
+        })
+        .sum();
+
+    Ok(sum.into())
+}
+
+fn has_neighbors(schematic: &Grid<char>, n: &Number) -> bool {
+    n.neighbours(schematic)
+        .iter()
+        .any(|pos| is_symbol(schematic[*pos]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solver;
+    use crate::y2023::day3::Day3;
+
+    #[test]
+    fn test_is_symbol() {
+        assert!(is_symbol('$'));
+        assert!(is_symbol('*'));
+        assert!(!is_symbol('.'));
+        assert!(!is_symbol('3'));
+    }
+
+    #[test]
+    fn test_has_neighbors() {
+        let schematic = Day3
+            .parse(
+                "\
+12.$
+..3.
+45*.",
+            )
+            .unwrap();
+        let number = |x, y, value| Number { x, y, value };
+        assert!(!has_neighbors(&schematic, &number(0, 0, 12)));
+        assert!(has_neighbors(&schematic, &number(2, 1, 3)));
+        assert!(has_neighbors(&schematic, &number(0, 2, 45)));
+    }
+
+    #[test]
+    fn test_schematics() {
+        let schematic = Day3
+            .parse(
+                "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..",
+            )
+            .unwrap();
+        assert_eq!(run(&schematic).unwrap(), "4361")
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use crate::grid::{Grid, Pos};
+use crate::solution::Answer;
+
+use super::find_numbers;
+
+pub fn run(schematic: &Grid<char>) -> anyhow::Result<Answer> {
+    let numbers = find_numbers(schematic)?;
+    let mut maybe_gears = build_starmap(schematic);
+    assert!(!numbers.is_empty());
+    for n in &numbers {
+        for pos in n.neighbours(schematic) {
+            maybe_gears.entry(pos).and_modify(|gear| {
+                gear.num_neighbors += 1;
+                gear.value *= n.value as usize;
+            });
+        }
+    }
+
+    let sum = maybe_gears
+        .iter()
+        .filter(|(_, gear)| gear.num_neighbors == 2)
+        .map(|(_, gear)| gear.value)
+        .sum::<usize>();
+
+    Ok(sum.into())
+}
+
+pub fn build_starmap(schematic: &Grid<char>) -> HashMap<Pos, Gear> {
+    schematic
+        .iter()
+        .filter(|(_, c)| **c == '*')
+        .map(|(pos, _)| {
+            let gear = Gear {
+                num_neighbors: 0,
+                value: 1,
+            };
+            (pos, gear)
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Gear {
+    pub num_neighbors: usize,
+    pub value: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solver;
+    use crate::y2023::day3::Day3;
+
+    #[test]
+    fn test_build_gears() {
+        let schematic = Day3
+            .parse(
+                "\
+12.$
+..3.
+45*.",
+            )
+            .unwrap();
+        let stars = build_starmap(&schematic);
+        assert_eq!(stars.len(), 1);
+        assert!(stars.contains_key(&(2, 2)));
+    }
+
+    #[test]
+    fn test_gears_sum() {
+        let schematic = Day3
+            .parse(
+                "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..",
+            )
+            .unwrap();
+        assert_eq!(run(&schematic).unwrap(), "467835")
+    }
+}
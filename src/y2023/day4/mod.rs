@@ -9,54 +9,89 @@ use nom::multi::{fold_many1, many1};
 use nom::sequence::{separated_pair, terminated, tuple};
 use nom::IResult;
 
-use crate::solution::Solution;
+use crate::context::Context;
+use crate::error::{parse_lines, ParseError};
+use crate::solution::{Answer, Example, Solution, Solver};
 
-pub fn run(input: &str) -> anyhow::Result<Solution> {
-    let cards = collect_cards(input);
-    let counts: Vec<usize> = cards.iter().map(ScratchCardNumbers::count).collect();
-    let part1_sum = counts.iter().map(score).sum::<usize>();
-
-    let mut card_count: Vec<usize> = counts.iter().map(|_| 1).collect();
-    for (id, probe) in counts.iter().enumerate() {
-        let our_count = *card_count.get(id).unwrap();
-        let probe_start = id + 1;
-        let probe_end = probe_start + probe;
-        for i in probe_start..probe_end {
-            if let Some(count) = card_count.get_mut(i) {
-                *count += our_count
+pub struct Day4;
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11",
+    part1: Some("13"),
+    part2: Some("30"),
+}];
+
+impl Solver for Day4 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn name(&self) -> &'static str {
+        "d4"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        let cards = collect_cards(input)?;
+        Ok(cards.iter().map(ScratchCardNumbers::count).collect())
+    }
+
+    fn part1(&self, counts: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        Ok(counts.iter().map(score).sum::<usize>().into())
+    }
+
+    fn part2(&self, counts: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        let mut card_count: Vec<usize> = counts.iter().map(|_| 1).collect();
+        for (id, probe) in counts.iter().enumerate() {
+            let our_count = *card_count.get(id).unwrap();
+            let probe_start = id + 1;
+            let probe_end = probe_start + probe;
+            for i in probe_start..probe_end {
+                if let Some(count) = card_count.get_mut(i) {
+                    *count += our_count
+                }
             }
         }
+        Ok(card_count.iter().sum::<usize>().into())
     }
-    let part2_sum = card_count.iter().sum::<usize>();
 
-    Ok(Solution {
-        part1: part1_sum.to_string(),
-        part2: part2_sum.to_string(),
-    })
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
 }
 
-fn collect_cards(input: &str) -> Vec<ScratchCardNumbers> {
-    let cards: Vec<ScratchCardNumbers> = input
-        .lines()
-        .filter_map(|line| match parse_card(line) {
-            Ok((_, card)) => Some(card),
-            Err(_) => None,
-        })
-        .map(|card| card.numbers)
-        .collect();
-    cards
+pub fn run(input: &str) -> anyhow::Result<Solution> {
+    Day4.solve(input)
+}
+
+pub fn collect_cards(input: &str) -> Result<Vec<ScratchCardNumbers>, ParseError> {
+    parse_lines(
+        input,
+        map(parse_card, |card| card.numbers),
+        "a card like `Card 1: 41 48 83 | 83 86 6`",
+    )
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Card {
-    id: u32,
-    numbers: ScratchCardNumbers,
+pub struct Card {
+    pub id: u32,
+    pub numbers: ScratchCardNumbers,
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct ScratchCardNumbers {
-    winning_numbers: Vec<u32>,
-    numbers: Vec<u32>,
+pub struct ScratchCardNumbers {
+    pub winning_numbers: Vec<u32>,
+    pub numbers: Vec<u32>,
 }
 fn score(count: &usize) -> usize {
     match count {
@@ -66,7 +101,7 @@ fn score(count: &usize) -> usize {
 }
 
 impl ScratchCardNumbers {
-    fn count(&self) -> usize {
+    pub fn count(&self) -> usize {
         self.numbers
             .iter()
             .filter(|n| self.winning_numbers.contains(n))
@@ -95,7 +130,7 @@ fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
     )(input)
 }
 
-fn parse_scratchcard_numbers(input: &str) -> IResult<&str, ScratchCardNumbers> {
+pub fn parse_scratchcard_numbers(input: &str) -> IResult<&str, ScratchCardNumbers> {
     map(separated_pair(numbers, tag("|"), numbers), |item| {
         ScratchCardNumbers {
             winning_numbers: item.0,
@@ -104,7 +139,7 @@ fn parse_scratchcard_numbers(input: &str) -> IResult<&str, ScratchCardNumbers> {
     })(input)
 }
 
-fn parse_card(input: &str) -> IResult<&str, Card> {
+pub fn parse_card(input: &str) -> IResult<&str, Card> {
     map(
         terminated(
             tuple((
@@ -174,7 +209,7 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
         assert_eq!(
-            collect_cards(input),
+            collect_cards(input).unwrap(),
             vec![
                 ScratchCardNumbers {
                     winning_numbers: vec![41, 48, 83, 86, 17],
@@ -204,6 +239,13 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
         );
     }
 
+    #[test]
+    fn test_collect_cards_rejects_garbage() {
+        let error = collect_cards("Card 1: 41 48 | 83 86\ngarbage\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "garbage");
+    }
+
     #[test]
     fn test_part1() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
@@ -1,6 +1,8 @@
 use std::collections::BTreeMap;
 use std::ops::Bound;
 
+use itertools::process_results;
+use log::debug;
 use nom::bytes::complete::tag;
 use nom::character::complete::{alpha1, newline, u64};
 use nom::combinator::{map, opt};
@@ -8,55 +10,132 @@ use nom::multi::{fold_many1, separated_list1};
 use nom::sequence::{delimited, preceded, separated_pair, terminated, tuple};
 use nom::IResult;
 
-use crate::solution::Solution;
+use crate::context::Context;
+use crate::error::parse_all;
+use crate::solution::{Answer, Example, Solution, Solver};
 
-type BTM = BTreeMap<u64, i64>;
+pub type BTM = BTreeMap<u64, i64>;
 
-pub fn run(input: &str) -> anyhow::Result<Solution> {
-    let (input, state) = parse_state(input).expect("failed to parse state");
-    assert_eq!(input, "");
-    let (seeds, translation_maps): (Vec<u64>, Vec<BTM>) = (state.seeds, state.translation_maps);
+pub struct Day5;
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4",
+    part1: Some("35"),
+    part2: Some("46"),
+}];
+
+impl Solver for Day5 {
+    type Parsed<'a> = State;
+
+    fn name(&self) -> &'static str {
+        "d5"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
 
-    let ranged: u64 = seeds
-        .chunks_exact(2)
-        .flat_map(|chunk| {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(parse_all(
+            input,
+            parse_state,
+            "seeds followed by `x-to-y map:` sections",
+        )?)
+    }
+
+    fn part1(&self, state: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        let part1 = state
+            .seeds
+            // Hack start: Solves a compiler complaint that I couldn't figure out how to fix
+            .chunks_exact(1)
+            .flat_map(|chunk| chunk[0]..=chunk[0])
+            // Hack end
+            .map(|seed| {
+                state
+                    .translation_maps
+                    .iter()
+                    .fold(seed, |seed, map| translate(&map, seed))
+            })
+            .min()
+            .unwrap();
+        Ok(part1.into())
+    }
+
+    fn part2(&self, state: &Self::Parsed<'_>, ctx: &Context) -> anyhow::Result<Answer> {
+        let seeds = state.seeds.chunks_exact(2).flat_map(|chunk| {
             let start = chunk[0] - 1;
             let end = chunk[0] + chunk[1];
-            println!("{} - {}", start, end);
+            debug!("seed range {} - {}", start, end);
             (start..end).collect::<Vec<u64>>()
-        })
-        .map(|seed| {
-            translation_maps
-                .iter()
-                .fold(seed, |seed, map| translate(&map, seed))
-        })
-        .min()
-        .unwrap();
-
-    let part1 = seeds
-        // Hack start: Solves a compiler complaint that I couldn't figure out how to fix
-        .chunks_exact(1)
-        .flat_map(|chunk| chunk[0]..=chunk[0])
-        // Hack end
-        .map(|seed| {
-            translation_maps
-                .iter()
-                .fold(seed, |seed, map| translate(&map, seed))
-        })
-        .min()
-        .unwrap();
-
-    Ok(Solution {
-        part1: part1.to_string(),
-        part2: ranged.to_string(),
-    })
+        });
+        let total = state
+            .seeds
+            .chunks_exact(2)
+            .map(|chunk| chunk[1] + 1)
+            .sum::<u64>();
+        let locations =
+            ctx.progress("seeds", total as usize, seeds)
+                .map(|seed| -> anyhow::Result<u64> {
+                    ctx.check()?;
+                    Ok(state
+                        .translation_maps
+                        .iter()
+                        .fold(seed, |seed, map| translate(&map, seed)))
+                });
+        let ranged: u64 = process_results(locations, |locations| locations.min())?.unwrap();
+        Ok(ranged.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+pub fn run(input: &str) -> anyhow::Result<Solution> {
+    Day5.solve(input)
 }
 
 fn parse_numbers(input: &str) -> IResult<&str, Vec<u64>> {
     separated_list1(tag(" "), u64)(input)
 }
 
-fn seeds(input: &str) -> IResult<&str, Vec<u64>> {
+pub fn seeds(input: &str) -> IResult<&str, Vec<u64>> {
     preceded(tag("seeds: "), parse_numbers)(input)
 }
 
@@ -115,11 +194,11 @@ fn map_names(input: &str) -> IResult<&str, MapNames> {
     )(input)
 }
 
-fn maps(input: &str) -> IResult<&str, Vec<BTM>> {
+pub fn maps(input: &str) -> IResult<&str, Vec<BTM>> {
     separated_list1(newline, preceded(map_names, category_map))(input)
 }
 
-fn parse_state(input: &str) -> IResult<&str, State> {
+pub fn parse_state(input: &str) -> IResult<&str, State> {
     map(
         tuple((preceded(opt(newline), seeds), preceded(opt(newline), maps))),
         |(seeds, translation_maps)| State {
@@ -129,7 +208,7 @@ fn parse_state(input: &str) -> IResult<&str, State> {
     )(input)
 }
 
-fn translate(map: &BTM, index: u64) -> u64 {
+pub fn translate(map: &BTM, index: u64) -> u64 {
     let cursor = map.upper_bound(Bound::Excluded(&index));
     match cursor.value() {
         Some(offset) => (index as i64 + offset) as u64,
@@ -138,9 +217,9 @@ fn translate(map: &BTM, index: u64) -> u64 {
 }
 
 #[derive(Debug, PartialEq)]
-struct State {
-    seeds: Vec<u64>,
-    translation_maps: Vec<BTM>,
+pub struct State {
+    pub seeds: Vec<u64>,
+    pub translation_maps: Vec<BTM>,
 }
 
 #[cfg(test)]
@@ -1,59 +1,107 @@
 use std::collections::HashMap;
 
-use nom::bytes::complete::{tag, take};
-use nom::character::complete::{newline, u32};
-use nom::combinator::map;
-use nom::multi::separated_list1;
+use nom::bytes::complete::tag;
+use nom::character::complete::{newline, one_of, u32};
+use nom::combinator::{map, recognize};
+use nom::multi::{count, separated_list1};
 use nom::sequence::separated_pair;
 use nom::IResult;
 
-use crate::solution::Solution;
+use crate::context::Context;
+use crate::error::parse_lines;
+use crate::solution::{Answer, Example, Solution, Solver};
 
-pub fn run(input: &str) -> anyhow::Result<Solution> {
-    let mut hands = hands(input).expect("failed to parse hands").1;
-    let _ = &hands.sort_by(|a, b| {
-        let a_hand_type = HandType::part1(&a.cards);
-        let b_hand_type = HandType::part1(&b.cards);
-        let a_hand = a.cards.iter().map(char_to_card).collect::<Vec<Card>>();
-        let b_hand = b.cards.iter().map(char_to_card).collect::<Vec<Card>>();
-        (a_hand_type, a_hand).cmp(&(b_hand_type, b_hand))
-    });
-    let part1 = hands
-        .iter()
-        .enumerate()
-        .fold(0, |acc, (i, hand)| acc + hand.bid * (i + 1) as u32);
-
-    let _ = &hands.sort_by(|a, b| {
-        let a_hand_type = HandType::part2(&a.cards);
-        let b_hand_type = HandType::part2(&b.cards);
-        let a_hand = a
-            .cards
+pub struct Day7;
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483",
+    part1: Some("6440"),
+    part2: Some("5905"),
+}];
+
+impl Solver for Day7 {
+    type Parsed<'a> = Vec<Hand>;
+
+    fn name(&self) -> &'static str {
+        "d7"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(parse_lines(
+            input,
+            hand,
+            "a hand and a bid like `32T3K 765`",
+        )?)
+    }
+
+    fn part1(&self, hands: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        let mut hands = hands.clone();
+        let _ = &hands.sort_by(|a, b| {
+            let a_hand_type = HandType::part1(&a.cards);
+            let b_hand_type = HandType::part1(&b.cards);
+            let a_hand = a.cards.iter().map(char_to_card).collect::<Vec<Card>>();
+            let b_hand = b.cards.iter().map(char_to_card).collect::<Vec<Card>>();
+            (a_hand_type, a_hand).cmp(&(b_hand_type, b_hand))
+        });
+        let part1 = hands
             .iter()
-            .map(char_to_card_part2)
-            .collect::<Vec<Card>>();
-        let b_hand = b
-            .cards
+            .enumerate()
+            .fold(0, |acc, (i, hand)| acc + hand.bid * (i + 1) as u32);
+        Ok(part1.into())
+    }
+
+    fn part2(&self, hands: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        let mut hands = hands.clone();
+        let _ = &hands.sort_by(|a, b| {
+            let a_hand_type = HandType::part2(&a.cards);
+            let b_hand_type = HandType::part2(&b.cards);
+            let a_hand = a
+                .cards
+                .iter()
+                .map(char_to_card_part2)
+                .collect::<Vec<Card>>();
+            let b_hand = b
+                .cards
+                .iter()
+                .map(char_to_card_part2)
+                .collect::<Vec<Card>>();
+            (a_hand_type, a_hand).cmp(&(b_hand_type, b_hand))
+        });
+
+        let part2 = hands
             .iter()
-            .map(char_to_card_part2)
-            .collect::<Vec<Card>>();
-        (a_hand_type, a_hand).cmp(&(b_hand_type, b_hand))
-    });
-
-    let part2 = hands
-        .iter()
-        .enumerate()
-        .fold(0, |acc, (i, hand)| acc + hand.bid * (i + 1) as u32);
-
-    Ok(Solution {
-        part1: part1.to_string(),
-        part2: part2.to_string(),
-    })
+            .enumerate()
+            .fold(0, |acc, (i, hand)| acc + hand.bid * (i + 1) as u32);
+        Ok(part2.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+pub fn run(input: &str) -> anyhow::Result<Solution> {
+    Day7.solve(input)
 }
 
-fn hand(input: &str) -> IResult<&str, Hand> {
+pub fn hand(input: &str) -> IResult<&str, Hand> {
     map(
         separated_pair(
-            map(take(5usize), |s: &str| s.bytes().collect::<Vec<u8>>()),
+            map(recognize(count(one_of(CARDS), 5)), |s: &str| {
+                s.bytes().collect::<Vec<u8>>()
+            }),
             tag(" "),
             u32,
         ),
@@ -61,11 +109,13 @@ fn hand(input: &str) -> IResult<&str, Hand> {
     )(input)
 }
 
-fn hands(input: &str) -> IResult<&str, Vec<Hand>> {
+pub fn hands(input: &str) -> IResult<&str, Vec<Hand>> {
     separated_list1(newline, hand)(input)
 }
 
-type Card = u8;
+pub type Card = u8;
+
+const CARDS: &str = "23456789TJQKA";
 
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
 enum HandType {
@@ -126,9 +176,9 @@ impl HandType {
 }
 
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
-struct Hand {
-    cards: Vec<u8>,
-    bid: u32,
+pub struct Hand {
+    pub cards: Vec<u8>,
+    pub bid: u32,
 }
 
 fn into_character_map(chars: Vec<u8>) -> HashMap<u8, u32> {
@@ -0,0 +1,511 @@
+use std::collections::HashMap;
+use std::iter::zip;
+use std::ops::ControlFlow::{Break, Continue};
+
+use anyhow::anyhow;
+use log::debug;
+use nom::bytes::complete::{tag, take};
+use nom::character::complete::{newline, one_of};
+use nom::combinator::{map, opt};
+use nom::multi::{fold_many1, many1};
+use nom::sequence::{delimited, separated_pair, terminated};
+use nom::IResult;
+use num::Integer;
+
+use crate::context::Context;
+use crate::error::{parse_all, ParseError};
+use crate::solution::{Answer, Example, Param, Solution, Solver};
+
+pub struct Day8;
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        input: "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)",
+        part1: Some("2"),
+        part2: None,
+    },
+    Example {
+        input: "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)",
+        part1: Some("6"),
+        part2: None,
+    },
+    Example {
+        input: "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+        part1: None,
+        part2: Some("6"),
+    },
+];
+
+const PARAMS: &[Param] = &[
+    Param {
+        name: "start",
+        default: "AAA",
+        doc: "Node part 1 starts from",
+    },
+    Param {
+        name: "end",
+        default: "ZZZ",
+        doc: "Node part 1 ends at",
+    },
+    Param {
+        name: "start-suffix",
+        default: "A",
+        doc: "Part 2 starts from every node ending in this",
+    },
+    Param {
+        name: "end-suffix",
+        default: "Z",
+        doc: "Part 2 ends at nodes ending in this",
+    },
+];
+
+impl Solver for Day8 {
+    type Parsed<'a> = (Vec<Instruction>, Graph<'a>);
+
+    fn name(&self) -> &'static str {
+        "d8"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        let (instructions, graph) = parse_all(
+            input,
+            instructions_and_graph,
+            "`LR` instructions, a blank line and nodes like `AAA = (BBB, CCC)`",
+        )?;
+        // Point at the first reference to a node that has no line of its own
+        let undefined = graph
+            .values()
+            .flat_map(|(left, right)| [*left, *right])
+            .filter(|target| !graph.contains_key(target))
+            .min_by_key(|target| target.as_ptr());
+        if let Some(target) = undefined {
+            return Err(ParseError::new(input, target, "a node defined in the map").into());
+        }
+        Ok((instructions, graph))
+    }
+
+    fn part1(
+        &self,
+        (instructions, graph): &Self::Parsed<'_>,
+        ctx: &Context,
+    ) -> anyhow::Result<Answer> {
+        let start: String = ctx.param("start")?;
+        let end: String = ctx.param("end")?;
+        for vertex in [&start, &end] {
+            if !graph.contains_key(vertex.as_str()) {
+                return Err(anyhow!("{} is not a node of the map", vertex));
+            }
+        }
+        Ok(part1(instructions, graph, &start, &end, ctx)?.into())
+    }
+
+    fn part2(
+        &self,
+        (instructions, graph): &Self::Parsed<'_>,
+        ctx: &Context,
+    ) -> anyhow::Result<Answer> {
+        let start_suffix: String = ctx.param("start-suffix")?;
+        let end_suffix: String = ctx.param("end-suffix")?;
+        Ok(part2(instructions, graph, &start_suffix, &end_suffix, ctx)?.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+}
+
+pub fn run(input: &str) -> anyhow::Result<Solution> {
+    Day8.solve(input)
+}
+
+type StartingVertex = u16;
+type InstructionIndex = u16;
+type PathLength = usize;
+type PathKey = (StartingVertex, InstructionIndex);
+type PathsMap = HashMap<PathKey, PathLength>;
+fn compute_paths(
+    instructions: &Vec<bool>,
+    graph: &Vec<[u16; 2]>,
+    starting_vertices: &Vec<u16>,
+    sentinels: &Vec<bool>,
+    ctx: &Context,
+) -> PathsMap {
+    // Every (node, instruction) pair comes up within this many steps, so a walk that hasn't
+    // come back to a sentinel by then never meets one
+    let limit = graph.len() * instructions.len() + 1;
+    ctx.progress("cycles", starting_vertices.len(), starting_vertices.iter())
+        .flat_map(|start| {
+            let mut visited: PathsMap = HashMap::new();
+            let _ = instructions
+                .iter()
+                .enumerate()
+                .cycle()
+                .enumerate()
+                .take(limit)
+                .try_fold(start.clone(), |current, (length, (offset, lr))| {
+                    let next = graph[current as usize][*lr as usize];
+                    let key = (*start, offset as u16);
+                    if !(visited.contains_key(&key)) {
+                        if sentinels[current as usize] {
+                            visited.insert(key, length);
+                        }
+                        Continue(next)
+                    } else {
+                        Break(current)
+                    }
+                });
+
+            visited
+        })
+        .collect()
+}
+
+fn part2(
+    instructions: &Vec<Instruction>,
+    graph: &Graph,
+    start_suffix: &str,
+    end_suffix: &str,
+    ctx: &Context,
+) -> anyhow::Result<PathLength> {
+    let mut sorted_keys: Vec<Vertex> = graph.keys().map(|k| *k).collect();
+    sorted_keys.sort();
+
+    let vertex_to_int: HashMap<Vertex, u16> =
+        HashMap::from_iter(sorted_keys.iter().enumerate().map(|(i, k)| (*k, i as u16)));
+    // let int_to_vertex: HashMap<u16, Vertex> =
+    //     HashMap::from_iter(sorted_keys.iter().enumerate().map(|(i, k)| (i as u16, *k)));
+    let left: Vec<u16> = sorted_keys
+        .iter()
+        .map(|k| graph[k].0)
+        .map(|k| vertex_to_int[k])
+        .collect();
+    let right: Vec<u16> = sorted_keys
+        .iter()
+        .map(|k| graph[k].1)
+        .map(|k| vertex_to_int[k])
+        .collect();
+    let both: Vec<[u16; 2]> = zip(left, right).map(|(l, r)| [l, r]).collect();
+    let sentinels: Vec<bool> = sorted_keys
+        .iter()
+        .map(|k| k.ends_with(end_suffix))
+        .collect();
+    let starting_vertices: Vec<u16> = graph
+        .keys()
+        .filter(|k| k.ends_with(start_suffix))
+        .map(|k| vertex_to_int[k])
+        .collect::<Vec<u16>>();
+    if starting_vertices.is_empty() {
+        return Err(anyhow!("no node ends in {}", start_suffix));
+    }
+    let instructions: Vec<bool> = instructions
+        .iter()
+        .map(|i| match i {
+            Instruction::Left => false,
+            Instruction::Right => true,
+        })
+        .collect();
+    let paths = compute_paths(&instructions, &both, &starting_vertices, &sentinels, ctx);
+    debug!("cycle lengths: {:?}", paths);
+    let mut lengths = paths.values().copied();
+    let first = lengths
+        .next()
+        .ok_or_else(|| anyhow!("no path reaches a node ending in {}", end_suffix))?;
+    lengths.try_fold(first, checked_lcm)
+}
+
+fn checked_lcm(a: PathLength, b: PathLength) -> anyhow::Result<PathLength> {
+    (a / a.gcd(&b)).checked_mul(b).ok_or_else(|| {
+        anyhow!(
+            "the paths only line up after more than {} steps",
+            PathLength::MAX
+        )
+    })
+}
+
+fn part1<'a>(
+    instructions: &Vec<Instruction>,
+    graph: &Graph<'a>,
+    start: Vertex<'a>,
+    end: Vertex<'a>,
+    ctx: &Context,
+) -> anyhow::Result<usize> {
+    // Past one step per (node, instruction) pair the walk has started going round in a loop
+    let limit = graph.len() * instructions.len();
+    let mut current: Vertex = start;
+    for (i, instruction) in instructions.iter().cycle().enumerate().take(limit) {
+        ctx.check()?;
+        current = match instruction {
+            Instruction::Left => graph[current].0,
+            Instruction::Right => graph[current].1,
+        };
+        if current == end {
+            return Ok(i + 1);
+        }
+    }
+    Err(anyhow!("{} never reaches {}", start, end))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Instruction {
+    Right,
+    Left,
+}
+
+#[inline]
+pub fn instruction(input: &str) -> IResult<&str, Instruction> {
+    map(one_of("LR"), |c| match c {
+        'L' => Instruction::Left,
+        'R' => Instruction::Right,
+        _ => unreachable!(),
+    })(input)
+}
+
+pub fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+    terminated(many1(instruction), opt(newline))(input)
+}
+
+pub type Vertex<'a> = &'a str;
+pub type Graph<'a> = HashMap<Vertex<'a>, (Vertex<'a>, Vertex<'a>)>;
+pub fn node<'a>(input: &'a str) -> IResult<&str, (Vertex<'a>, (Vertex<'a>, Vertex<'a>))> {
+    separated_pair(
+        take(3usize),
+        tag(" = "),
+        delimited(
+            tag("("),
+            separated_pair(take(3usize), tag(", "), take(3usize)),
+            tag(")"),
+        ),
+    )(input)
+}
+
+pub fn graph<'a>(input: &'a str) -> IResult<&str, Graph<'a>> {
+    fold_many1(
+        terminated(node, opt(newline)),
+        Graph::new,
+        |mut acc, (k, v)| {
+            acc.insert(k, v);
+            acc
+        },
+    )(input)
+}
+
+pub fn instructions_and_graph<'a>(input: &'a str) -> IResult<&str, (Vec<Instruction>, Graph<'a>)> {
+    separated_pair(instructions, newline, graph)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Params;
+    use crate::solution::{Parts, Runner};
+
+    #[test]
+    fn test_parse_instruction() {
+        assert_eq!(instruction("R"), Ok(("", Instruction::Right)));
+        assert_eq!(instruction("L"), Ok(("", Instruction::Left)));
+    }
+
+    #[test]
+    fn test_parse_instructions() {
+        assert_eq!(
+            instructions("LLR"),
+            Ok((
+                "",
+                vec![Instruction::Left, Instruction::Left, Instruction::Right]
+            ))
+        );
+        assert_eq!(
+            instructions("RLRL"),
+            Ok((
+                "",
+                vec![
+                    Instruction::Right,
+                    Instruction::Left,
+                    Instruction::Right,
+                    Instruction::Left
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_node() {
+        let input = "AAA = (BBB, CCC)";
+        assert_eq!(node(input), Ok(("", ("AAA", ("BBB", "CCC")))));
+    }
+    #[test]
+    fn test_parse_nodes() {
+        let input = "AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+";
+        let expected = Graph::from_iter(vec![("AAA", ("BBB", "CCC")), ("BBB", ("DDD", "EEE"))]);
+        assert_eq!(graph(input), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_instructions_and_graph() {
+        let input = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)";
+        let expected = (
+            vec![Instruction::Right, Instruction::Left],
+            Graph::from_iter(vec![("AAA", ("BBB", "CCC")), ("BBB", ("DDD", "EEE"))]),
+        );
+        assert_eq!(instructions_and_graph(input), Ok(("", expected)));
+    }
+
+    ///LLR
+    //
+    // AAA = (BBB, BBB)
+    // BBB = (AAA, ZZZ)
+    // ZZZ = (ZZZ, ZZZ)
+    #[test]
+    fn test_paths() {
+        let instructions = vec![false, false, true];
+        let graph = vec![[1u16, 1], [0, 2], [2, 2]];
+        let starting_vertices = vec![0u16];
+        let sentinels = vec![false, false, true];
+        let actual = compute_paths(
+            &instructions,
+            &graph,
+            &starting_vertices,
+            &sentinels,
+            &Context::default(),
+        );
+        let expected: PathsMap = HashMap::from_iter(vec![((0, 0), 6), ((0, 1), 7), ((0, 2), 8)]);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+        assert_eq!(run(input).unwrap().part1, "2");
+        let input = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        assert_eq!(run(input).unwrap().part1, "6");
+    }
+    #[test]
+    fn test_part2() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+        let result = Day8.run(input, Parts::Two, &Context::default(), &mut Vec::new());
+        assert_eq!(
+            result.unwrap().part2.unwrap().unwrap(),
+            Answer::from(6usize)
+        );
+    }
+
+    #[test]
+    fn test_part2_errors() {
+        let part2 = |input: &str, start_suffix: &str| {
+            let params = Params::from([(
+                "d8".to_string(),
+                [("start-suffix".to_string(), start_suffix.to_string())].into(),
+            )]);
+            let ctx = Context::default().with_params(params);
+            Day8.run(input, Parts::Two, &ctx, &mut Vec::new())
+                .and_then(|result| result.part2.unwrap())
+        };
+        assert_eq!(
+            part2(EXAMPLES[2].input, "Q").unwrap_err().to_string(),
+            "no node ends in Q"
+        );
+        assert_eq!(
+            part2(EXAMPLES[0].input, "G").unwrap_err().to_string(),
+            "no path reaches a node ending in Z"
+        );
+        assert_eq!(checked_lcm(4, 6).unwrap(), 12);
+        assert!(checked_lcm(PathLength::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn test_undefined_node() {
+        let error = Day8
+            .parse("LR\n\nAAA = (BBB, QQQ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)")
+            .map(|_| ())
+            .unwrap_err()
+            .downcast::<ParseError>()
+            .unwrap();
+        assert_eq!((error.line, error.column), (3, 13));
+        assert_eq!(error.found, "QQQ)");
+    }
+
+    #[test]
+    fn test_part1_unreachable() {
+        let part1 = |start: &str, end: &str| {
+            let params = Params::from([(
+                "d8".to_string(),
+                [
+                    ("start".to_string(), start.to_string()),
+                    ("end".to_string(), end.to_string()),
+                ]
+                .into(),
+            )]);
+            let ctx = Context::default().with_params(params);
+            Day8.run(EXAMPLES[0].input, Parts::One, &ctx, &mut Vec::new())
+                .and_then(|result| result.part1.unwrap())
+        };
+        assert_eq!(part1("AAA", "CCC").unwrap(), Answer::from(1usize));
+        assert_eq!(
+            part1("XYZ", "ZZZ").unwrap_err().to_string(),
+            "XYZ is not a node of the map"
+        );
+        assert_eq!(
+            part1("ZZZ", "AAA").unwrap_err().to_string(),
+            "ZZZ never reaches AAA"
+        );
+    }
+}
@@ -5,24 +5,68 @@ use nom::character::complete::{i32 as i32p, newline};
 use nom::multi::separated_list1;
 use nom::IResult;
 
-use crate::solution::Solution;
+use crate::context::Context;
+use crate::error::parse_all;
+use crate::solution::{Answer, Example, Solution, Solver};
 
-type Reading = i32;
-type Readings = Vec<Reading>;
+pub type Reading = i32;
+pub type Readings = Vec<Reading>;
+
+pub struct Day9;
+
+const EXAMPLES: &[Example] = &[Example {
+    input: "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45",
+    part1: Some("114"),
+    part2: Some("2"),
+}];
+
+impl Solver for Day9 {
+    type Parsed<'a> = Vec<Readings>;
+
+    fn name(&self) -> &'static str {
+        "d9"
+    }
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(parse_all(
+            input,
+            histories,
+            "lines of space separated numbers",
+        )?)
+    }
+
+    fn part1(&self, histories: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        Ok(part1(histories).into())
+    }
+
+    fn part2(&self, histories: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        Ok(part2(histories).into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
 
 pub fn run(input: &str) -> anyhow::Result<Solution> {
-    let (_, histories) = histories(input).expect("failed to parse");
-    Ok(Solution {
-        part1: part1(&histories).to_string(),
-        part2: part2(&histories).to_string(),
-    })
+    Day9.solve(input)
 }
 
-fn numbers(input: &str) -> IResult<&str, Readings> {
+pub fn numbers(input: &str) -> IResult<&str, Readings> {
     separated_list1(tag(" "), i32p)(input)
 }
 
-fn histories(input: &str) -> IResult<&str, Vec<Readings>> {
+pub fn histories(input: &str) -> IResult<&str, Vec<Readings>> {
     separated_list1(newline, numbers)(input)
 }
 
@@ -37,7 +81,7 @@ fn part2(histories: &Vec<Readings>) -> Reading {
     })
 }
 
-fn predict_next(history: &Readings) -> Reading {
+pub fn predict_next(history: &Readings) -> Reading {
     let diff: Readings = history.iter().tuple_windows().map(|(a, b)| b - a).collect();
     if diff.iter().all(|x| *x == 0) {
         history.last().unwrap().clone()
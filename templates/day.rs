@@ -0,0 +1,83 @@
+use nom::character::complete::{newline, u64};
+use nom::multi::separated_list1;
+use nom::IResult;
+
+use crate::context::Context;
+use crate::error::parse_all;
+use crate::solution::{Answer, Example, Solution, Solver};
+
+pub struct Day__DAY__;
+
+// TODO: paste the puzzle's example and fill in its answers once they are known
+const EXAMPLES: &[Example] = &[Example {
+    input: "\
+1
+2
+3",
+    part1: None,
+    part2: None,
+}];
+
+impl Solver for Day__DAY__ {
+    type Parsed<'a> = Vec<u64>;
+
+    fn name(&self) -> &'static str {
+        "d__DAY__"
+    }
+
+    fn year(&self) -> u16 {
+        __YEAR__
+    }
+
+    fn day(&self) -> u8 {
+        __DAY__
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(parse_all(input, numbers, "one number per line")?)
+    }
+
+    fn part1(&self, _numbers: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        Ok(Answer::Unsolved)
+    }
+
+    fn part2(&self, _numbers: &Self::Parsed<'_>, _ctx: &Context) -> anyhow::Result<Answer> {
+        Ok(Answer::Unsolved)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        EXAMPLES
+    }
+}
+
+pub fn run(input: &str) -> anyhow::Result<Solution> {
+    Day__DAY__.solve(input)
+}
+
+pub fn numbers(input: &str) -> IResult<&str, Vec<u64>> {
+    separated_list1(newline, u64)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = EXAMPLES[0].input;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(numbers(EXAMPLE), Ok(("", vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn test_part1() {
+        // TODO: the example's expected answer
+        assert_eq!(run(EXAMPLE).unwrap().part1, "unsolved");
+    }
+
+    #[test]
+    fn test_part2() {
+        // TODO: the example's expected answer
+        assert_eq!(run(EXAMPLE).unwrap().part2, "unsolved");
+    }
+}